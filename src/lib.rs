@@ -8,18 +8,34 @@
     unused_qualifications
 )]
 use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap, TryReserveError, VecDeque},
+    convert::TryFrom,
+    fmt,
+    iter::{Copied, FromIterator},
+    marker::PhantomData,
     mem,
-    ops::{Index, IndexMut, RangeBounds},
+    num::TryFromIntError,
+    ops::{AddAssign, Bound, Index, IndexMut, Range, RangeBounds, RangeInclusive},
     slice::{Iter, IterMut, SliceIndex},
+    sync::{Mutex, PoisonError},
     vec::{Drain, IntoIter},
 };
 
+#[cfg(feature = "cow-order")]
+use std::sync::Arc;
+
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
 /// Container for multiple elements sorted by a certain `isize` order.
 ///
 /// Every element `T` is tagged with an associated `isize`. The `isize` value decides the relative
 /// ordering of the elements. All manipulations keep the items `T` ordered according to the `isize`
 /// values from lowest to highest.
 ///
+/// With the `cow-order` feature, clones share their orders until one of them changes the orders.
+///
 /// ```
 /// use isize_vec::IsizeVec;
 ///
@@ -34,64 +50,294 @@ use std::{
 ///     println!("{}", value);
 /// }
 /// ```
-#[derive(Clone, Debug)]
 pub struct IsizeVec<T> {
     items: Vec<T>,
-    order: Vec<isize>,
+    order: Orders,
+    tiebreak: Tiebreak,
+    balanced: bool,
+    on_change: Option<Mutex<Listener>>,
+}
+
+/// Placement of an inserted value relative to existing values of the same order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Tiebreak {
+    /// Prepend values of signed order, append values of unsigned order.
+    #[default]
+    Sign,
+    /// Always append after existing values of the same order.
+    Append,
+    /// Always prepend before existing values of the same order.
+    Prepend,
+}
+
+impl Tiebreak {
+    fn appends(self, relative: isize) -> bool {
+        match self {
+            Tiebreak::Sign => relative >= 0,
+            Tiebreak::Append => true,
+            Tiebreak::Prepend => false,
+        }
+    }
+}
+
+/// Change to an [IsizeVec], reported to the callback set with [IsizeVec::on_change].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeEvent {
+    /// An element was inserted at the given index.
+    Inserted(usize),
+    /// The element at the given index was removed.
+    Removed(usize),
+    /// All elements were removed.
+    Cleared,
+}
+
+/// Callback set with [IsizeVec::on_change].
+///
+/// The callback must be [Send] so that an [IsizeVec] holding one can still be sent to another
+/// thread. It does not need to be [Sync], as it is only ever called through `&mut IsizeVec`.
+pub type Listener = Box<dyn FnMut(ChangeEvent) + Send>;
+
+/// Fire `event` on the callback, if one is set.
+fn emit(on_change: &mut Option<Mutex<Listener>>, event: ChangeEvent) {
+    if let Some(callback) = on_change {
+        let callback = callback.get_mut().unwrap_or_else(PoisonError::into_inner);
+        callback(event);
+    }
+}
+
+/// Backing storage of the orders, shared between clones with the `cow-order` feature.
+#[cfg(not(feature = "cow-order"))]
+type Orders = Vec<isize>;
+#[cfg(feature = "cow-order")]
+type Orders = Arc<Vec<isize>>;
+
+#[cfg(not(feature = "cow-order"))]
+fn orders_mut(order: &mut Orders) -> &mut Vec<isize> {
+    order
+}
+
+/// Get mutable access to the orders, cloning them first if they are shared.
+///
+/// The clone keeps the capacity of the shared orders, so balanced capacities stay balanced.
+#[cfg(feature = "cow-order")]
+fn orders_mut(order: &mut Orders) -> &mut Vec<isize> {
+    if Arc::get_mut(order).is_none() {
+        let mut unshared = Vec::with_capacity(order.capacity());
+        unshared.extend_from_slice(order);
+        *order = Arc::new(unshared);
+    }
+    Arc::make_mut(order)
+}
+
+#[cfg(not(feature = "cow-order"))]
+fn into_orders(order: Orders) -> Vec<isize> {
+    order
+}
+
+/// Take ownership of the orders, cloning them if they are shared.
+#[cfg(feature = "cow-order")]
+fn into_orders(order: Orders) -> Vec<isize> {
+    Arc::try_unwrap(order).unwrap_or_else(|shared| (*shared).clone())
 }
 
 impl<T> Default for IsizeVec<T> {
     fn default() -> Self {
         Self {
             items: Vec::new(),
-            order: Vec::new(),
+            order: Orders::default(),
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            on_change: None,
+        }
+    }
+}
+
+impl<T: Clone> Clone for IsizeVec<T> {
+    /// Clone the elements of this vector. The change callback is not cloned.
+    fn clone(&self) -> Self {
+        let mut items = self.items.clone();
+        if cfg!(feature = "cow-order") && self.balanced {
+            // The orders are shared and keep their capacity, so match it.
+            items.reserve_exact(self.order.capacity() - items.len());
+        }
+        Self {
+            items,
+            order: self.order.clone(),
+            tiebreak: self.tiebreak,
+            balanced: self.balanced,
+            on_change: None,
         }
     }
 }
 
+impl<T: fmt::Debug> fmt::Debug for IsizeVec<T> {
+    /// Print each item paired with its order, as in `IsizeVec [(5, 'a'), (10, 'b')]`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IsizeVec ")?;
+        f.debug_list().entries(self.iter_with_order()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for IsizeVec<T> {
+    /// Compare the `(order, item)` pairs of both vectors. The configuration is not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.order[..] == other.order[..] && self.items == other.items
+    }
+}
+
+impl<T: Eq> Eq for IsizeVec<T> {}
+
+impl<T: PartialOrd> PartialOrd for IsizeVec<T> {
+    /// Compare the `(order, item)` pairs of both vectors lexicographically.
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter_with_order().partial_cmp(other.iter_with_order())
+    }
+}
+
+impl<T: Ord> Ord for IsizeVec<T> {
+    /// Compare the `(order, item)` pairs of both vectors lexicographically.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter_with_order().cmp(other.iter_with_order())
+    }
+}
+
 impl<T> IsizeVec<T> {
     /// Create a new vector.
     pub fn new() -> Self {
         Self {
             items: Vec::new(),
-            order: Vec::new(),
+            order: Orders::default(),
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            on_change: None,
+        }
+    }
+
+    /// Create a new vector with space for at least `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+            order: Orders::from(Vec::with_capacity(capacity)),
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            on_change: None,
+        }
+    }
+
+    /// Create a vector of `n` items produced by `f`, all at order `relative`.
+    pub fn fill_with<F>(relative: isize, n: usize, f: F) -> Self
+    where
+        F: FnMut() -> T,
+    {
+        let mut items = Vec::with_capacity(n);
+        items.extend(std::iter::repeat_with(f).take(n));
+        Self {
+            items,
+            order: Orders::from(vec![relative; n]),
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            on_change: None,
         }
     }
 
+    /// Set a callback that is fired whenever an element is inserted into or removed from this
+    /// vector, or the vector is cleared.
+    ///
+    /// Every method adding or removing elements fires one event per element, with indices that
+    /// are valid when the events are applied in sequence. Methods that only move elements or
+    /// change their orders, like [IsizeVec::swap] or [IsizeVec::set_order], fire no events.
+    ///
+    /// The callback is a [Listener], see there for why it must be [Send]. It replaces any
+    /// previously set one and is not carried over by [Clone].
+    pub fn on_change(&mut self, callback: Listener) {
+        self.on_change = Some(Mutex::new(callback));
+    }
+
     /// Get an iterator to the values.
     #[inline]
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.items.iter()
     }
 
     /// Get an iterator to the values (mutable).
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         self.items.iter_mut()
     }
 
+    /// Get an iterator to the order values, from lowest to highest.
+    #[inline]
+    pub fn iter_order(&self) -> Copied<Iter<'_, isize>> {
+        self.order.iter().copied()
+    }
+
+    /// Get the orders and the items as two slices of equal length.
+    pub fn parts(&self) -> (&[isize], &[T]) {
+        (&self.order, &self.items)
+    }
+
+    /// Get an iterator to the values along with their orders.
+    #[inline]
+    pub fn iter_with_order(&self) -> impl Iterator<Item = (isize, &T)> + '_ {
+        self.order.iter().copied().zip(self.items.iter())
+    }
+
+    /// Get an iterator to the values along with their orders, from highest to lowest order.
+    #[inline]
+    pub fn iter_rev_with_order(&self) -> impl Iterator<Item = (isize, &T)> + '_ {
+        self.order
+            .iter()
+            .copied()
+            .rev()
+            .zip(self.items.iter().rev())
+    }
+
+    /// Get an iterator to the values along with their orders (mutable).
+    ///
+    /// Orders are yielded by value, so they can not be changed through this iterator.
+    #[inline]
+    pub fn iter_mut_with_order(&mut self) -> impl Iterator<Item = (isize, &mut T)> + '_ {
+        self.order.iter().copied().zip(self.items.iter_mut())
+    }
+
     /// Push a value to the end of the vector, with `relative: isize::MAX`.
     pub fn push(&mut self, item: T) -> usize {
-        self.items.push(item);
-        self.order.push(isize::MAX);
-        self.items.len() - 1
+        let index = self.items.len();
+        self.insert_at(index, isize::MAX, item);
+        index
     }
 
     /// Remove the last element from this vector.
     pub fn pop(&mut self) -> Option<(T, isize)> {
         if !self.items.is_empty() {
-            Some((self.items.pop().unwrap(), self.order.pop().unwrap()))
+            let popped = (
+                self.items.pop().unwrap(),
+                orders_mut(&mut self.order).pop().unwrap(),
+            );
+            self.notify(ChangeEvent::Removed(self.items.len()));
+            Some(popped)
         } else {
             None
         }
     }
 
     /// Create a drain iterator.
-    pub fn drain<R>(&mut self, range: R) -> Drain<T>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, T>
     where
         R: Clone + RangeBounds<usize>,
     {
-        self.order.drain(range.clone());
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.items.len(),
+        };
+        orders_mut(&mut self.order).drain(range.clone());
+        self.notify_removed(start..end);
         self.items.drain(range)
     }
 
@@ -117,11 +363,19 @@ impl<T> IsizeVec<T> {
 
     /// Return the backing vector and clear this container.
     pub fn extract(&mut self) -> Vec<T> {
-        self.order.clear();
-        mem::replace(&mut self.items, Vec::new())
+        orders_mut(&mut self.order).clear();
+        self.notify(ChangeEvent::Cleared);
+        mem::take(&mut self.items)
+    }
+
+    /// Consume this vector, returning the items sorted by order.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.items
     }
 
     /// Find the index at which positive elements start.
+    ///
+    /// Returns `0` on an empty vector.
     pub fn first_positive(&self) -> usize {
         self.first_right_of(-1)
     }
@@ -130,331 +384,4411 @@ impl<T> IsizeVec<T> {
     ///
     /// The value `relative` indicates where the value will be put in the list relative to other
     /// values. If two values have the same `relative` value, then the value will be prepended if it
-    /// is signed, and appended if unsigned.
+    /// is signed, and appended if unsigned, unless another [Tiebreak] was set with
+    /// [IsizeVecBuilder::tiebreak].
     ///
     /// Returns the index of insertion.
     pub fn insert(&mut self, relative: isize, item: T) -> usize {
-        match self.order.binary_search(&relative) {
-            Ok(exact) => {
-                if relative >= 0 {
-                    match self.order[exact..]
-                        .iter()
-                        .enumerate()
-                        .find(|(_, &x)| x != relative)
-                    {
-                        Some((idx, _)) => {
-                            self.items.insert(exact + idx, item);
-                            self.order.insert(exact + idx, relative);
-                            exact + idx
-                        }
-                        None => {
-                            self.items.push(item);
-                            self.order.push(relative);
-                            self.items.len() - 1
-                        }
-                    }
-                } else {
-                    match self.order[..exact]
-                        .iter()
-                        .rev()
-                        .enumerate()
-                        .find(|(_, &x)| x != relative)
-                    {
-                        Some((idx, _)) => {
-                            self.items.insert(exact - idx, item);
-                            self.order.insert(exact - idx, relative);
-                            exact - idx
-                        }
-                        None => {
-                            self.items.insert(0, item);
-                            self.order.insert(0, relative);
-                            0
-                        }
-                    }
-                }
-            }
-            Err(order) => {
-                self.items.insert(order, item);
-                self.order.insert(order, relative);
-                order
-            }
+        let index = self.insertion_index(relative);
+        self.insert_at(index, relative, item);
+        index
+    }
+
+    /// Find the index [IsizeVec::insert] would place an element of order `relative` at.
+    ///
+    /// Applies the same tie-break policy without modifying the vector.
+    pub fn insertion_index(&self, relative: isize) -> usize {
+        if self.tiebreak.appends(relative) {
+            self.upper_bound(relative)
+        } else {
+            self.lower_bound(relative)
         }
     }
 
     /// Remove the given index from the vector.
     pub fn remove(&mut self, index: usize) -> (T, isize) {
-        (self.items.remove(index), self.order.remove(index))
+        let removed = (
+            self.items.remove(index),
+            orders_mut(&mut self.order).remove(index),
+        );
+        self.notify(ChangeEvent::Removed(index));
+        removed
+    }
+
+    /// Remove all elements from the vector.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        orders_mut(&mut self.order).clear();
+        self.notify(ChangeEvent::Cleared);
     }
 
     /// Find the first index to the right of the relative list.
+    ///
+    /// This is `O(log n)` regardless of how many elements share an order, and `O(1)` returning `0`
+    /// on an empty vector. Same as [IsizeVec::upper_bound].
     pub fn first_right_of(&self, relative: isize) -> usize {
-        match self.order.binary_search(&relative) {
-            Ok(exact) => self.order[exact..]
-                .iter()
-                .enumerate()
-                .find(|(_, &x)| x != relative)
-                .map(|(index, _)| exact + index)
-                .unwrap_or_else(|| self.order.len()),
-            Err(order) => order,
+        self.upper_bound(relative)
+    }
+
+    /// Find the first index that is not to the left of the relative list.
+    ///
+    /// This is the number of elements ordered strictly below `relative`. Same as
+    /// [IsizeVec::lower_bound].
+    pub fn first_left_of(&self, relative: isize) -> usize {
+        self.lower_bound(relative)
+    }
+
+    /// Find the first index whose order is at least `relative`.
+    pub fn lower_bound(&self, relative: isize) -> usize {
+        self.order.partition_point(|&x| x < relative)
+    }
+
+    /// Find the first index whose order is greater than `relative`.
+    ///
+    /// This is `O(1)` and returns `0` on an empty vector.
+    pub fn upper_bound(&self, relative: isize) -> usize {
+        if self.order.is_empty() {
+            return 0;
         }
+        self.order.partition_point(|&x| x <= relative)
     }
 
     /// Swap two elements in the list. Associated order is swapped.
+    ///
+    /// Unless both elements have the same order, this breaks the ordering of the vector. Use
+    /// [IsizeVec::swap_items_only] to exchange the items while keeping the orders in place.
     pub fn swap(&mut self, a: usize, b: usize) {
         self.items.swap(a, b);
+        orders_mut(&mut self.order).swap(a, b);
+    }
+
+    /// Swap two elements like [IsizeVec::swap], returning `false` instead of panicking if either
+    /// index is out of bounds.
+    pub fn checked_swap(&mut self, a: usize, b: usize) -> bool {
+        if a < self.len() && b < self.len() {
+            self.swap(a, b);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Swap the items of two elements in the list, keeping the orders in place.
+    ///
+    /// The two items exchange their orders, so the vector stays sorted.
+    pub fn swap_items_only(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+    }
+
+    /// Swap the items at `index` and `index + 1`, so the two neighbors exchange priorities.
+    ///
+    /// The orders stay in place, so the vector stays sorted. Returns `false` without changing
+    /// anything if `index + 1` is out of bounds.
+    pub fn swap_adjacent(&mut self, index: usize) -> bool {
+        match index.checked_add(1) {
+            Some(next) if next < self.len() => {
+                self.items.swap(index, next);
+                true
+            }
+            _ => false,
+        }
     }
 
     /// Same as [Vec::retain].
+    ///
+    /// Both backing vectors are compacted in a single `O(n)` pass.
     pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(&T) -> bool,
     {
-        let mut removed = 0;
-        let mut idx = 0;
-        let order = &mut self.order;
-        self.items.retain(|x| {
-            idx += 1;
-            if (f)(x) {
-                true
-            } else {
-                order.remove(idx - 1 - removed);
-                removed += 1;
-                false
-            }
-        });
-    }
-}
-
-impl<T, I> Index<I> for IsizeVec<T>
-where
-    I: SliceIndex<[T]>,
-{
-    type Output = <I as SliceIndex<[T]>>::Output;
-    fn index(&self, index: I) -> &<Vec<T> as Index<I>>::Output {
-        &self.items[index]
+        self.retain_indexed(|_, x| f(x));
     }
-}
 
-impl<T, I> IndexMut<I> for IsizeVec<T>
-where
-    I: SliceIndex<[T]>,
-{
-    fn index_mut(&mut self, index: I) -> &mut <Vec<T> as Index<I>>::Output {
-        &mut self.items[index]
+    /// Split this vector into the elements ordered below `threshold` and those at or above it.
+    ///
+    /// Both halves keep their relative ordering.
+    pub fn split_at_order(mut self, threshold: isize) -> (IsizeVec<T>, IsizeVec<T>) {
+        let upper = self.split_off_by_order(threshold);
+        (self, upper)
     }
-}
 
-impl<T> IntoIterator for IsizeVec<T> {
-    type Item = T;
-    type IntoIter = IntoIter<T>;
-    fn into_iter(self) -> IntoIter<T> {
-        self.items.into_iter()
+    /// Move the elements ordered at or above `threshold` into a new vector.
+    ///
+    /// The elements ordered below `threshold` stay in this vector.
+    pub fn split_off_by_order(&mut self, threshold: isize) -> IsizeVec<T> {
+        let index = self.lower_bound(threshold);
+        let end = self.items.len();
+        let mut upper = self.empty_like();
+        upper.items = self.items.split_off(index);
+        upper.order = Orders::from(orders_mut(&mut self.order).split_off(index));
+        self.notify_removed(index..end);
+        upper
     }
-}
 
-impl<'a, T> IntoIterator for &'a IsizeVec<T> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
-    fn into_iter(self) -> Iter<'a, T> {
-        self.items.iter()
+    /// Insert each pair, replacing the item of an element with the same exact order.
+    ///
+    /// This gives last-writer-wins semantics keyed on the order. If several elements already share
+    /// the order, only the first of them is replaced.
+    pub fn merge_keep_last<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (isize, T)>,
+    {
+        for (relative, item) in iter {
+            let index = self.first_left_of(relative);
+            if self.order.get(index) == Some(&relative) {
+                self.items[index] = item;
+            } else {
+                self.insert_at(index, relative, item);
+            }
+        }
     }
-}
 
-impl<'a, T> IntoIterator for &'a mut IsizeVec<T> {
-    type Item = &'a mut T;
-    type IntoIter = IterMut<'a, T>;
-    fn into_iter(self) -> IterMut<'a, T> {
-        self.items.iter_mut()
+    /// Insert a batch of values into this vector.
+    ///
+    /// The resulting layout is the same as calling [IsizeVec::insert] for each pair in turn, but
+    /// the batch is sorted once and merged into the existing elements in a single pass, which is
+    /// `O(n + m log m)` instead of `O(n * m)` and beats repeated inserts for large batches.
+    /// Both backing vectors are grown at most once.
+    pub fn insert_batch<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (isize, T)>,
+    {
+        let tiebreak = self.tiebreak;
+        let iter = iter.into_iter();
+        let mut batch = Vec::with_capacity(iter.size_hint().0);
+        batch.extend(iter.enumerate());
+        batch.sort_by(|(a_index, (a, _)), (b_index, (b, _))| {
+            a.cmp(b).then_with(|| {
+                if tiebreak.appends(*a) {
+                    a_index.cmp(b_index)
+                } else {
+                    b_index.cmp(a_index)
+                }
+            })
+        });
+        self.merge_sorted(batch.into_iter().map(|(_, pair)| pair));
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::IsizeVec;
 
-    #[quickcheck_macros::quickcheck]
-    fn inserting_appends_or_prepends(relative: isize, values: usize) {
-        let mut vector = IsizeVec::new();
+    /// Merge an already sorted batch into this vector in a single pass.
+    ///
+    /// Batch elements of equal order keep their relative position within the batch, and are placed
+    /// after or before existing elements of that order according to the tie-break policy.
+    ///
+    /// Existing elements ordered before the whole batch stay in place, and the backing vectors are
+    /// only grown if they lack room for the batch.
+    fn merge_sorted<I>(&mut self, batch: I)
+    where
+        I: ExactSizeIterator<Item = (isize, T)>,
+    {
+        let mut batch = batch.peekable();
+        let start = match batch.peek() {
+            Some(&(relative, _)) => self.insertion_index(relative),
+            None => return,
+        };
+        self.reserve(batch.len());
 
-        for value in 0..values {
-            vector.insert(relative, value);
+        let tiebreak = self.tiebreak;
+        let order = orders_mut(&mut self.order);
+        let moved = order
+            .drain(start..)
+            .zip(self.items.drain(start..))
+            .collect::<Vec<_>>();
+        let mut existing = moved.into_iter().peekable();
+        loop {
+            let take_existing = match (existing.peek(), batch.peek()) {
+                (Some((a, _)), Some((b, _))) => a < b || (a == b && tiebreak.appends(*a)),
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            let (relative, item) = if take_existing {
+                existing.next().unwrap()
+            } else {
+                emit(&mut self.on_change, ChangeEvent::Inserted(order.len()));
+                batch.next().unwrap()
+            };
+            order.push(relative);
+            self.items.push(item);
         }
+    }
 
-        if relative >= 0 {
-            let mut value = 0;
-            for item in vector.iter() {
-                assert_eq!(value, *item);
-                value += 1;
-            }
-            assert_eq!(value, values);
+    /// Remove the given index from the vector, without panicking.
+    ///
+    /// Returns `Err(self.len())` if the index is out of bounds.
+    pub fn checked_remove(&mut self, index: usize) -> Result<(T, isize), usize> {
+        if index < self.items.len() {
+            Ok(self.remove(index))
         } else {
-            let mut value = values;
-            for item in vector.iter() {
-                value -= 1;
-                assert_eq!(value, *item);
-            }
-            assert_eq!(value, 0);
+            Err(self.items.len())
         }
     }
 
-    #[quickcheck_macros::quickcheck]
-    fn insertion_behaves_as_ordering(orders: Vec<(u8, isize)>) {
-        let mut vector = IsizeVec::new();
+    /// Get the first element to the right of the relative list, along with its order.
+    ///
+    /// Returns `None` if no element is ordered after `relative`.
+    pub fn item_right_of(&self, relative: isize) -> Option<(isize, &T)> {
+        let index = self.first_right_of(relative);
+        self.items.get(index).map(|item| (self.order[index], item))
+    }
 
-        for (item, order) in &orders {
-            vector.insert(*order, item);
-        }
+    /// Remove all elements ordered below `threshold`.
+    ///
+    /// Returns the number of elements removed.
+    pub fn prune_below(&mut self, threshold: isize) -> usize {
+        let index = self.first_left_of(threshold);
+        self.drain(..index);
+        index
+    }
 
-        let mut unsigned = orders
-            .iter()
-            .cloned()
-            .filter(|(_, o)| o >= &0)
-            .collect::<Vec<_>>();
+    /// Remove all elements ordered above `threshold`.
+    ///
+    /// Returns the number of elements removed.
+    pub fn prune_above(&mut self, threshold: isize) -> usize {
+        let index = self.first_right_of(threshold);
+        let removed = self.items.len() - index;
+        self.truncate(index);
+        removed
+    }
 
-        let mut signed = orders
-            .iter()
-            .cloned()
-            .filter(|(_, o)| o < &0)
-            .collect::<Vec<_>>();
+    /// Clear this vector and refill it from `pairs`, keeping the allocated capacity.
+    ///
+    /// The pairs need not be sorted, they are placed as if by [IsizeVec::insert_batch].
+    pub fn reset_to<I>(&mut self, pairs: I)
+    where
+        I: IntoIterator<Item = (isize, T)>,
+    {
+        self.clear();
+        self.insert_batch(pairs);
+    }
 
-        unsigned.sort_by(|a, b| a.1.cmp(&b.1));
-        signed.reverse();
-        signed.sort_by(|a, b| a.1.cmp(&b.1));
+    /// Get the rank of an order, the number of elements ordered strictly below `relative`.
+    pub fn rank_of_order(&self, relative: isize) -> usize {
+        self.first_left_of(relative)
+    }
 
-        for (idx, (item, _)) in signed.iter().chain(unsigned.iter()).enumerate() {
-            assert_eq!(vector[idx], item);
-        }
+    /// Get the `k`-th element counting from the lowest order, along with its order.
+    pub fn nth_from_min(&self, k: usize) -> Option<(isize, &T)> {
+        self.items.get(k).map(|item| (self.order[k], item))
     }
 
-    #[quickcheck_macros::quickcheck]
-    fn first_right_of_size(orders: Vec<isize>) {
-        let mut vector = IsizeVec::new();
+    /// Get the `k`-th element counting from the highest order, along with its order.
+    pub fn nth_from_max(&self, k: usize) -> Option<(isize, &T)> {
+        let index = self.items.len().checked_sub(k)?.checked_sub(1)?;
+        Some((self.order[index], &self.items[index]))
+    }
 
-        for order in &orders {
-            vector.insert(*order, ());
-        }
+    /// Get the items whose order falls within `range`.
+    pub fn range_by_order<R>(&self, range: R) -> &[T]
+    where
+        R: RangeBounds<isize>,
+    {
+        let span = self.span_by_order(range);
+        &self.items[span]
+    }
 
-        assert_eq!(vector.first_right_of(isize::MAX), vector.len());
+    /// Get the items whose order falls within `range` (mutable).
+    ///
+    /// The orders themselves are left untouched, so mutating the items can not break the ordering
+    /// of the vector. Callers must however not rely on any ordering the items carry on their own.
+    pub fn range_by_order_mut<R>(&mut self, range: R) -> &mut [T]
+    where
+        R: RangeBounds<isize>,
+    {
+        let span = self.span_by_order(range);
+        &mut self.items[span]
     }
 
-    #[test]
-    fn basic() {
-        let mut vector = IsizeVec::new();
+    /// Find the index span of the elements whose order falls within `range`.
+    fn span_by_order<R>(&self, range: R) -> Range<usize>
+    where
+        R: RangeBounds<isize>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(&relative) => self.lower_bound(relative),
+            Bound::Excluded(&relative) => self.upper_bound(relative),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&relative) => self.upper_bound(relative),
+            Bound::Excluded(&relative) => self.lower_bound(relative),
+            Bound::Unbounded => self.order.len(),
+        };
+        start..end.max(start)
+    }
+
+    /// Insert a value unless an element with the exact order `relative` already exists.
+    ///
+    /// On collision the index of the first existing element with that order is returned, together
+    /// with the rejected `item`. Otherwise the value is inserted and its index returned with `None`.
+    pub fn insert_or_get(&mut self, relative: isize, item: T) -> (usize, Option<T>) {
+        let index = self.first_left_of(relative);
+        if self.order.get(index) == Some(&relative) {
+            (index, Some(item))
+        } else {
+            (self.insert(relative, item), None)
+        }
+    }
+
+    /// Create a draining iterator yielding `(order, item)` pairs from the lowest order.
+    ///
+    /// Elements that are not consumed before the iterator is dropped stay in the vector.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T> {
+        DrainSorted::new(self)
+    }
+
+    /// Merge several sorted vectors into one in `O(N log k)`.
+    ///
+    /// Elements of equal order keep their relative position within their source, and elements of
+    /// equal order from different sources are placed in source order, lowest index first. The
+    /// result takes the configuration of the first source.
+    pub fn merge_all(mut sources: Vec<IsizeVec<T>>) -> IsizeVec<T> {
+        let capacity = sources.iter().map(IsizeVec::len).sum();
+        let mut merged = sources
+            .first()
+            .map_or_else(IsizeVec::new, IsizeVec::empty_like);
+        merged.reserve(capacity);
+
+        let mut heap = BinaryHeap::with_capacity(sources.len());
+        for (index, source) in sources.iter_mut().enumerate() {
+            source.items.reverse();
+            orders_mut(&mut source.order).reverse();
+            if let Some(&relative) = source.order.last() {
+                heap.push(Reverse((relative, index)));
+            }
+        }
+
+        while let Some(Reverse((_, index))) = heap.pop() {
+            let source = &mut sources[index];
+            let (item, relative) = source.pop().unwrap();
+            merged.items.push(item);
+            orders_mut(&mut merged.order).push(relative);
+            if let Some(&relative) = source.order.last() {
+                heap.push(Reverse((relative, index)));
+            }
+        }
+
+        merged
+    }
+
+    /// Find the index of the first element with order `relative` whose item matches `pred`.
+    ///
+    /// Only the elements of that order are searched.
+    pub fn find_in_order<F>(&self, relative: isize, pred: F) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let span = self.indices_of_order(relative);
+        let start = span.start;
+        self.items[span]
+            .iter()
+            .position(pred)
+            .map(|index| start + index)
+    }
+
+    /// Shrink the gaps between consecutive distinct orders to at most `max_gap`.
+    ///
+    /// The lowest order is kept as is, and elements that share an order keep sharing one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_gap` is less than one.
+    pub fn clamp_order_gaps(&mut self, max_gap: isize) {
+        assert!(max_gap >= 1, "max_gap must be at least one");
+        let mut previous = match self.order.first() {
+            Some(&first) => first,
+            None => return,
+        };
+        let order = orders_mut(&mut self.order);
+        for index in 1..order.len() {
+            let current = order[index];
+            let gap = current
+                .checked_sub(previous)
+                .map_or(max_gap, |gap| gap.min(max_gap));
+            previous = current;
+            order[index] = order[index - 1] + gap;
+        }
+    }
+
+    fn notify(&mut self, event: ChangeEvent) {
+        emit(&mut self.on_change, event);
+    }
+
+    /// Notify the removal of the elements that were at the indices in `removed`, first to last.
+    fn notify_removed(&mut self, removed: Range<usize>) {
+        for _ in removed.clone() {
+            self.notify(ChangeEvent::Removed(removed.start));
+        }
+    }
+
+    /// Remove and return the elements at the indices in `range`.
+    fn drain_pairs(&mut self, range: Range<usize>) -> Vec<(isize, T)> {
+        let pairs = orders_mut(&mut self.order)
+            .drain(range.clone())
+            .zip(self.items.drain(range.clone()))
+            .collect();
+        self.notify_removed(range);
+        pairs
+    }
+
+    /// Remove the elements from index `len` on.
+    fn truncate(&mut self, len: usize) {
+        let end = self.items.len();
+        if len < end {
+            self.items.truncate(len);
+            orders_mut(&mut self.order).truncate(len);
+            self.notify_removed(len..end);
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    ///
+    /// If capacities are balanced, both backing vectors end up with the same capacity.
+    pub fn reserve(&mut self, additional: usize) {
+        if self.balanced {
+            let additional = self.balanced_additional(additional);
+            self.items.reserve_exact(additional);
+            orders_mut(&mut self.order).reserve_exact(additional);
+        } else {
+            self.items.reserve(additional);
+            orders_mut(&mut self.order).reserve(additional);
+        }
+    }
+
+    /// Try to reserve capacity for at least `additional` more elements.
+    ///
+    /// If the second backing vector fails to grow, the first is shrunk back to its previous
+    /// capacity so both stay consistent.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let capacity = self.items.capacity();
+        if self.balanced {
+            let additional = self.balanced_additional(additional);
+            self.items.try_reserve_exact(additional)?;
+            if let Err(error) = orders_mut(&mut self.order).try_reserve_exact(additional) {
+                self.items.shrink_to(capacity);
+                return Err(error);
+            }
+            return Ok(());
+        }
+        self.items.try_reserve(additional)?;
+        if let Err(error) = orders_mut(&mut self.order).try_reserve(additional) {
+            self.items.shrink_to(capacity);
+            return Err(error);
+        }
+        Ok(())
+    }
+
+    /// Keep only the elements whose order falls within `range`.
+    ///
+    /// This is equivalent to `prune_below(range.start)` followed by `prune_above(range.end - 1)`.
+    pub fn keep_order_band(&mut self, range: Range<isize>) {
+        let span = self.span_by_order(range);
+        self.truncate(span.end);
+        self.drain(..span.start);
+    }
+
+    /// Get the highest order in this vector.
+    pub fn max_order(&self) -> Option<isize> {
+        self.order.last().copied()
+    }
+
+    /// Get the lowest order in this vector.
+    pub fn min_order(&self) -> Option<isize> {
+        self.order.first().copied()
+    }
+
+    /// Get the range from the lowest to the highest order in this vector.
+    pub fn order_extent(&self) -> Option<RangeInclusive<isize>> {
+        Some(self.min_order()?..=self.max_order()?)
+    }
+
+    /// Append a value to the end of the vector in `O(1)`.
+    ///
+    /// Fails if `relative` is lower than [IsizeVec::max_order], as the value would then not belong
+    /// at the end.
+    pub fn push_back(&mut self, relative: isize, item: T) -> Result<usize, OrderError> {
+        if self.max_order().is_some_and(|max| relative < max) {
+            return Err(OrderError::Unsorted);
+        }
+        let index = self.items.len();
+        self.insert_at(index, relative, item);
+        Ok(index)
+    }
+
+    /// Estimate the heap memory in bytes allocated by this vector.
+    ///
+    /// With the `cow-order` feature, orders shared between clones are counted in every clone.
+    pub fn memory_usage(&self) -> usize {
+        self.items.capacity() * size_of::<T>() + self.order.capacity() * size_of::<isize>()
+    }
+
+    /// Remove consecutive equal items, keeping the first of each run.
+    ///
+    /// Since the vector is sorted, the kept item is the one with the lowest order in its run.
+    pub fn dedup_items(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_items_with(false, |a, b| a == b);
+    }
+
+    /// Remove consecutive items that resolve to the same key, keeping the first of each run.
+    pub fn dedup_items_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: PartialEq,
+        F: FnMut(&T) -> K,
+    {
+        self.dedup_items_with(false, |a, b| key(a) == key(b));
+    }
+
+    /// Remove consecutive equal items, keeping the last of each run.
+    ///
+    /// Since the vector is sorted, the kept item is the one with the highest order in its run.
+    pub fn dedup_items_keep_last(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_items_with(true, |a, b| a == b);
+    }
+
+    /// Merge adjacent items while `f(left, right)` returns a merged item.
+    ///
+    /// The merged item replaces both and keeps the order of `left`, and is then compared with the
+    /// next item.
+    pub fn coalesce<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T, &T) -> Option<T>,
+    {
+        if self.items.is_empty() {
+            return;
+        }
+        let order = orders_mut(&mut self.order);
+        let mut write = 1;
+        for read in 1..self.items.len() {
+            if let Some(merged) = f(&self.items[write - 1], &self.items[read]) {
+                self.items[write - 1] = merged;
+                emit(&mut self.on_change, ChangeEvent::Removed(write));
+            } else {
+                self.items.swap(write, read);
+                order.swap(write, read);
+                write += 1;
+            }
+        }
+        self.items.truncate(write);
+        order.truncate(write);
+    }
+
+    fn dedup_items_with<F>(&mut self, keep_last: bool, mut same: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        if self.items.is_empty() {
+            return;
+        }
+        let order = orders_mut(&mut self.order);
+        let mut write = 1;
+        for read in 1..self.items.len() {
+            if !same(&self.items[write - 1], &self.items[read]) {
+                self.items.swap(write, read);
+                order.swap(write, read);
+                write += 1;
+            } else if keep_last {
+                self.items.swap(write - 1, read);
+                order.swap(write - 1, read);
+                emit(&mut self.on_change, ChangeEvent::Removed(write - 1));
+            } else {
+                emit(&mut self.on_change, ChangeEvent::Removed(write));
+            }
+        }
+        self.items.truncate(write);
+        order.truncate(write);
+    }
+
+    /// Find the order of the first occurrence of `value`.
+    ///
+    /// Items are not keyed by value, so this is a linear scan in `O(n)`.
+    pub fn order_of(&self, value: &T) -> Option<isize>
+    where
+        T: PartialEq,
+    {
+        self.items
+            .iter()
+            .position(|item| item == value)
+            .map(|index| self.order[index])
+    }
+
+    /// Find the first index with an order greater than or equal to `relative`.
+    ///
+    /// Same as [IsizeVec::lower_bound].
+    pub fn first_ge(&self, relative: isize) -> usize {
+        self.lower_bound(relative)
+    }
+
+    /// Find the first index with an order greater than `relative`.
+    ///
+    /// Same as [IsizeVec::upper_bound].
+    pub fn first_gt(&self, relative: isize) -> usize {
+        self.upper_bound(relative)
+    }
+
+    /// Find the last index with an order less than or equal to `relative`.
+    pub fn last_le(&self, relative: isize) -> Option<usize> {
+        self.first_gt(relative).checked_sub(1)
+    }
+
+    /// Find the last index with an order less than `relative`.
+    pub fn last_lt(&self, relative: isize) -> Option<usize> {
+        self.first_ge(relative).checked_sub(1)
+    }
+
+    /// Insert a value unless an equal item already exists with the same order.
+    ///
+    /// Returns the index of insertion, or `None` if the value was skipped.
+    pub fn insert_if_absent(&mut self, relative: isize, item: T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        if self.find_in_order(relative, |x| *x == item).is_some() {
+            None
+        } else {
+            Some(self.insert(relative, item))
+        }
+    }
+
+    /// Create a draining iterator yielding `(order, item)` pairs from the highest order.
+    ///
+    /// Each element is popped from the back in `O(1)`. Elements that are not consumed before the
+    /// iterator is dropped stay in the vector.
+    pub fn drain_sorted_rev(&mut self) -> impl Iterator<Item = (isize, T)> + '_ {
+        std::iter::from_fn(move || self.pop().map(|(item, relative)| (relative, item)))
+    }
+
+    /// Collect the runs of elements sharing an order into `(order, items)` groups.
+    pub fn grouped(&self) -> Vec<(isize, Vec<&T>)> {
+        let mut groups: Vec<(isize, Vec<&T>)> = Vec::new();
+        for (&relative, item) in self.order.iter().zip(&self.items) {
+            match groups.last_mut() {
+                Some((last, group)) if *last == relative => group.push(item),
+                _ => groups.push((relative, vec![item])),
+            }
+        }
+        groups
+    }
+
+    /// Consume this vector into owned `(order, items)` groups for each run sharing an order.
+    pub fn into_grouped(self) -> Vec<(isize, Vec<T>)> {
+        let mut groups: Vec<(isize, Vec<T>)> = Vec::new();
+        for (relative, item) in into_orders(self.order).into_iter().zip(self.items) {
+            match groups.last_mut() {
+                Some((last, group)) if *last == relative => group.push(item),
+                _ => groups.push((relative, vec![item])),
+            }
+        }
+        groups
+    }
+
+    /// Check the invariants of this vector, listing every violation found.
+    pub fn validate(&self) -> Result<(), Vec<Invariant>> {
+        let mut violations = Vec::new();
+        if self.items.len() != self.order.len() {
+            violations.push(Invariant::LengthMismatch);
+        }
+        for (at, pair) in self.order.windows(2).enumerate() {
+            if pair[0] > pair[1] {
+                violations.push(Invariant::NotSorted { at });
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Insert a value right after the first item matching `pred`.
+    ///
+    /// The new order lies strictly between the order of the matched item and the next distinct
+    /// order, or is one above it if there is no such order. If no integer fits in between, the
+    /// value gets the order of the matched item and is placed after all items of that order.
+    ///
+    /// Returns the index of insertion, or `None` if no item matches.
+    pub fn insert_after_item<F>(&mut self, pred: F, item: T) -> Option<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let found = self.items.iter().position(pred)?;
+        let relative = self.order[found];
+        let index = self.first_right_of(relative);
+        let new_order = match self.order.get(index) {
+            Some(&next) => midpoint(relative, next),
+            None => relative.checked_add(1),
+        };
+        self.insert_at(index, new_order.unwrap_or(relative), item);
+        Some(index)
+    }
+
+    fn insert_at(&mut self, index: usize, relative: isize, item: T) {
+        self.grow_for_insert();
+        self.items.insert(index, item);
+        orders_mut(&mut self.order).insert(index, relative);
+        self.notify(ChangeEvent::Inserted(index));
+    }
+
+    /// Change the order of the element at `index`, moving it to its sorted position.
+    ///
+    /// Only the elements between the old and new position are shifted. Among elements of the same
+    /// order, the moved element is placed according to the tie-break policy, like
+    /// [IsizeVec::insert]. This holds even if the order is unchanged, so the element may move to
+    /// the other end of its run.
+    ///
+    /// Returns the new index of the element.
+    pub fn set_order(&mut self, index: usize, new_order: isize) -> usize {
+        let appends = self.tiebreak.appends(new_order);
+        let before = |&x: &isize| x < new_order || (appends && x == new_order);
+        let order = orders_mut(&mut self.order);
+        let old_order = mem::replace(&mut order[index], new_order);
+        if new_order > old_order || (new_order == old_order && appends) {
+            let offset = order[index + 1..].partition_point(before);
+            self.items[index..=index + offset].rotate_left(1);
+            order[index..=index + offset].rotate_left(1);
+            index + offset
+        } else {
+            let target = order[..index].partition_point(before);
+            self.items[target..=index].rotate_right(1);
+            order[target..=index].rotate_right(1);
+            target
+        }
+    }
+
+    /// Split this vector by the items matching `pred` and those that do not.
+    ///
+    /// Every element keeps its order, so both vectors stay sorted.
+    pub fn partition<F>(self, mut pred: F) -> (IsizeVec<T>, IsizeVec<T>)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut matching = self.empty_like();
+        let mut rest = self.empty_like();
+        for (relative, item) in into_orders(self.order).into_iter().zip(self.items) {
+            let target = if pred(&item) {
+                &mut matching
+            } else {
+                &mut rest
+            };
+            target.insert_at(target.len(), relative, item);
+        }
+        (matching, rest)
+    }
+
+    /// Join this vector with `other` on equal orders.
+    ///
+    /// Both sorted sequences are walked in a single merge-join pass, yielding a triple for each
+    /// order present in both. Runs of equal orders are paired element by element, so the shorter
+    /// run decides how many triples an order yields.
+    pub fn zip_by_order<'a, U>(
+        &'a self,
+        other: &'a IsizeVec<U>,
+    ) -> impl Iterator<Item = (isize, &'a T, &'a U)> + 'a {
+        let (mut left, mut right) = (0, 0);
+        std::iter::from_fn(move || {
+            while left < self.order.len() && right < other.order.len() {
+                let (a, b) = (self.order[left], other.order[right]);
+                if a < b {
+                    left += 1;
+                } else if a > b {
+                    right += 1;
+                } else {
+                    left += 1;
+                    right += 1;
+                    return Some((a, &self.items[left - 1], &other.items[right - 1]));
+                }
+            }
+            None
+        })
+    }
+
+    /// Get the next distinct order above `relative`, if any.
+    pub fn next_order_after(&self, relative: isize) -> Option<isize> {
+        self.order.get(self.first_right_of(relative)).copied()
+    }
+
+    /// Get the nearest distinct order below `relative`, if any.
+    pub fn prev_order_before(&self, relative: isize) -> Option<isize> {
+        let index = self.first_left_of(relative).checked_sub(1)?;
+        Some(self.order[index])
+    }
+
+    /// Returns whether both backing vectors have the same capacity.
+    ///
+    /// This always holds for zero-sized `T`, whose vectors never allocate.
+    pub fn balanced_capacity(&self) -> bool {
+        size_of::<T>() == 0 || self.items.capacity() == self.order.capacity()
+    }
+
+    /// Create an empty vector with the same configuration as this one.
+    fn empty_like(&self) -> Self {
+        let mut vector = IsizeVec::new();
+        vector.tiebreak = self.tiebreak;
+        vector.balanced = self.balanced;
+        vector
+    }
+
+    /// Compute how many elements to reserve so both vectors reach the same capacity.
+    ///
+    /// If the total would overflow, `additional` is returned unchanged so the backing vectors
+    /// report the capacity overflow themselves.
+    fn balanced_additional(&self, additional: usize) -> usize {
+        let capacity = self.items.capacity().max(self.order.capacity());
+        match self.items.len().checked_add(additional) {
+            Some(total) => total.max(capacity) - self.items.len(),
+            None => additional,
+        }
+    }
+
+    /// Grow both vectors together before inserting, if capacities are balanced.
+    fn grow_for_insert(&mut self) {
+        let len = self.items.len();
+        if self.balanced && (len == self.items.capacity() || len == self.order.capacity()) {
+            self.reserve(len.max(4));
+        }
+    }
+
+    /// Binary search the items for `value`.
+    ///
+    /// Only meaningful if the items are sorted by their own ordering, which holds when they were
+    /// assigned orders in the same sequence. Otherwise the result is unspecified, see
+    /// [slice::binary_search].
+    pub fn binary_search(&self, value: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.items.binary_search(value)
+    }
+
+    /// Binary search the items for a key extracted by `f`.
+    ///
+    /// Only meaningful if the extracted keys are sorted, see [slice::binary_search_by_key].
+    pub fn binary_search_by_key<B, F>(&self, key: &B, f: F) -> Result<usize, usize>
+    where
+        B: Ord,
+        F: FnMut(&T) -> B,
+    {
+        self.items.binary_search_by_key(key, f)
+    }
+
+    /// Returns the capacity of the backing item vector.
+    pub fn items_capacity(&self) -> usize {
+        self.items.capacity()
+    }
+
+    /// Returns the capacity of the backing order vector.
+    pub fn orders_capacity(&self) -> usize {
+        self.order.capacity()
+    }
+
+    /// Same as [IsizeVec::retain], but also passes the index of each item.
+    ///
+    /// The index is the position of the item before any element was removed.
+    pub fn retain_indexed<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &T) -> bool,
+    {
+        let order = orders_mut(&mut self.order);
+        let mut write = 0;
+        for read in 0..self.items.len() {
+            if f(read, &self.items[read]) {
+                self.items.swap(write, read);
+                order.swap(write, read);
+                write += 1;
+            } else {
+                emit(&mut self.on_change, ChangeEvent::Removed(write));
+            }
+        }
+        self.items.truncate(write);
+        order.truncate(write);
+    }
+
+    /// Same as [IsizeVec::retain], but pushes every removed `(order, item)` pair to `removed`.
+    ///
+    /// Both the kept and the removed elements stay in ascending order. This is a single `O(n)` pass
+    /// that keeps the allocation of both backing vectors, and only allocates to track where the
+    /// removed elements end up until they are moved out.
+    pub fn retain_into<F>(&mut self, mut f: F, removed: &mut Vec<(isize, T)>)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let order = orders_mut(&mut self.order);
+        // Removed elements collect between `write` and `read`. Moving a kept element in front of
+        // them rotates the oldest one to the back, so `pending` tracks their original sequence.
+        let mut pending = VecDeque::new();
+        let mut write = 0;
+        for read in 0..self.items.len() {
+            if f(&self.items[read]) {
+                self.items.swap(write, read);
+                order.swap(write, read);
+                pending.rotate_left(pending.len().min(1));
+                write += 1;
+            } else {
+                pending.push_back(read - write);
+                emit(&mut self.on_change, ChangeEvent::Removed(write));
+            }
+        }
+
+        let first = removed.len();
+        removed.extend(order.drain(write..).zip(self.items.drain(write..)));
+        let tail = &mut removed[first..];
+        for slot in 0..tail.len() {
+            while pending[slot] != slot {
+                let target = pending[slot];
+                tail.swap(slot, target);
+                pending.swap(slot, target);
+            }
+        }
+    }
+
+    /// Insert a value into this vector, same as [IsizeVec::insert].
+    ///
+    /// [IsizeVec::insert] already finds the insertion point by binary search, so inserting into a
+    /// long run of equal orders costs `O(log n)` plus the shift.
+    ///
+    /// Returns the index of insertion.
+    pub fn insert_balanced(&mut self, relative: isize, item: T) -> usize {
+        self.insert(relative, item)
+    }
+
+    /// Clone and push every item of `items` to the end of the vector, with `relative: isize::MAX`.
+    pub fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(items.len());
+        for item in items {
+            self.push(item.clone());
+        }
+    }
+
+    /// Split off the lowest-order element, returning it with its order and the remaining items.
+    pub fn split_first(&self) -> Option<((isize, &T), &[T])> {
+        let (first, rest) = self.items.split_first()?;
+        Some(((self.order[0], first), rest))
+    }
+
+    /// Split off the highest-order element, returning it with its order and the remaining items.
+    pub fn split_last(&self) -> Option<((isize, &T), &[T])> {
+        let (last, rest) = self.items.split_last()?;
+        Some(((self.order[rest.len()], last), rest))
+    }
+
+    /// Remove every element with the order `relative`, returning their items.
+    pub fn remove_all_with_order(&mut self, relative: isize) -> Vec<T> {
+        let span = self.indices_of_order(relative);
+        self.drain(span).collect()
+    }
+
+    /// Returns the number of elements with a signed order.
+    pub fn count_negative(&self) -> usize {
+        self.first_positive()
+    }
+
+    /// Returns the number of elements with an unsigned order.
+    pub fn count_nonnegative(&self) -> usize {
+        self.len() - self.first_positive()
+    }
+
+    /// Create a cursor starting at the lowest-order element.
+    pub fn cursor(&mut self) -> Cursor<'_, T> {
+        Cursor {
+            vector: self,
+            index: 0,
+        }
+    }
+
+    /// Clone this vector into an [I32Vec], which stores the orders in half the space on 64-bit
+    /// targets.
+    ///
+    /// Fails if any order does not fit in an `i32`.
+    pub fn to_i32_orders(&self) -> Result<I32Vec<T>, TryFromIntError>
+    where
+        T: Clone,
+    {
+        Ok(I32Vec {
+            items: self.items.clone(),
+            order: self
+                .order
+                .iter()
+                .map(|&relative| i32::try_from(relative))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Merge a batch of pairs that is already sorted by order into this vector in `O(n + m)`.
+    ///
+    /// The result is the same as inserting the pairs one at a time with [IsizeVec::insert], so runs
+    /// of equal order end up reversed where the tie-break policy prepends.
+    ///
+    /// The batch is only checked for sortedness in debug builds.
+    pub fn merge_presorted(&mut self, mut pairs: Vec<(isize, T)>) {
+        debug_assert!(
+            pairs.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "pairs are not sorted by order"
+        );
+        let mut start = 0;
+        while start < pairs.len() {
+            let relative = pairs[start].0;
+            let end = start
+                + pairs[start..]
+                    .iter()
+                    .take_while(|pair| pair.0 == relative)
+                    .count();
+            if !self.tiebreak.appends(relative) {
+                pairs[start..end].reverse();
+            }
+            start = end;
+        }
+        self.merge_sorted(pairs.into_iter());
+    }
+
+    /// Map and filter the elements into a new vector, keeping the order of each kept element.
+    pub fn filter_map<U, F>(&self, mut f: F) -> IsizeVec<U>
+    where
+        F: FnMut(isize, &T) -> Option<U>,
+    {
+        let mut mapped = IsizeVec::new();
+        mapped.tiebreak = self.tiebreak;
+        mapped.balanced = self.balanced;
+        for (relative, item) in self.iter_with_order() {
+            if let Some(item) = f(relative, item) {
+                mapped.insert_at(mapped.len(), relative, item);
+            }
+        }
+        mapped
+    }
+
+    /// Get an iterator over the gaps between consecutive distinct orders.
+    ///
+    /// Yields `(lower, upper, gap)` with `gap = upper - lower`, saturating at `isize::MAX`.
+    pub fn order_gaps(&self) -> impl Iterator<Item = (isize, isize, isize)> + '_ {
+        self.order
+            .windows(2)
+            .filter(|pair| pair[0] != pair[1])
+            .map(|pair| (pair[0], pair[1], pair[1].saturating_sub(pair[0])))
+    }
+
+    /// Get an order strictly between the orders at indices `left` and `right`.
+    ///
+    /// Returns `None` if either index is out of bounds or no integer lies strictly between the two
+    /// orders, in which case the orders need to be compacted or spread out first.
+    pub fn order_between(&self, left: usize, right: usize) -> Option<isize> {
+        let low = *self.order.get(left)?;
+        let high = *self.order.get(right)?;
+        midpoint(low, high)
+    }
+
+    /// Replace every order with the one at the same index in `new_orders` and re-sort once.
+    ///
+    /// Elements whose new orders are equal keep their current relative position. This is
+    /// `O(n log n)` instead of calling [IsizeVec::set_order] for every element.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_orders.len() != self.len()`.
+    pub fn reassign_orders(&mut self, new_orders: &[isize]) {
+        assert_eq!(
+            new_orders.len(),
+            self.len(),
+            "new orders must have the same length as the vector"
+        );
+        orders_mut(&mut self.order).copy_from_slice(new_orders);
+        self.resort();
+    }
+
+    /// Edit the orders and items in place through `f`, then re-sort once.
+    ///
+    /// Elements whose orders end up equal keep their relative position from after the edit.
+    pub fn edit_orders<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut [isize], &mut [T]),
+    {
+        f(orders_mut(&mut self.order), &mut self.items);
+        self.resort();
+    }
+
+    /// Stable sort both backing vectors together by order.
+    fn resort(&mut self) {
+        if self.order.windows(2).all(|pair| pair[0] <= pair[1]) {
+            return;
+        }
+        let order = orders_mut(&mut self.order);
+        let mut pairs = order
+            .drain(..)
+            .zip(self.items.drain(..))
+            .collect::<Vec<_>>();
+        pairs.sort_by_key(|pair| pair.0);
+        for (relative, item) in pairs {
+            order.push(relative);
+            self.items.push(item);
+        }
+    }
+
+    /// Consume this vector into `(order, item)` pairs from the highest order to the lowest.
+    pub fn into_iter_rev(self) -> impl Iterator<Item = (isize, T)> {
+        into_orders(self.order)
+            .into_iter()
+            .rev()
+            .zip(self.items.into_iter().rev())
+    }
+
+    /// Find the first index whose order is greater than the order after it.
+    ///
+    /// Returns `None` if the orders are sorted. This is an `O(n)` diagnostic for vectors whose
+    /// ordering was broken, for instance by [IsizeVec::swap].
+    pub fn first_unsorted_index(&self) -> Option<usize> {
+        self.order.windows(2).position(|pair| pair[0] > pair[1])
+    }
+
+    /// Keep only the elements with an order in `range`, returning the removed items.
+    ///
+    /// The first vector holds the items ordered below the range and the second those ordered at or
+    /// above its end, both in ascending order.
+    pub fn trim_to_range(&mut self, range: Range<isize>) -> (Vec<T>, Vec<T>) {
+        let span = self.span_by_order(range);
+        let above = self
+            .drain_pairs(span.end..self.items.len())
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect();
+        let below = self.drain(..span.start).collect();
+        (below, above)
+    }
+
+    /// Get the range of indices holding elements of order `relative`.
+    ///
+    /// The range is empty, positioned where such elements would go, if there are none.
+    pub fn indices_of_order(&self, relative: isize) -> Range<usize> {
+        self.lower_bound(relative)..self.upper_bound(relative)
+    }
+
+    /// Add `delta` to the order of every element with an order in `range`.
+    ///
+    /// Fails without changing anything if a shifted order would overflow, or would move past the
+    /// order of an element outside the range.
+    pub fn shift_orders_in_range(
+        &mut self,
+        range: Range<isize>,
+        delta: isize,
+    ) -> Result<(), OrderError> {
+        let span = self.span_by_order(range);
+        if span.is_empty() {
+            return Ok(());
+        }
+        let low = self.order[span.start]
+            .checked_add(delta)
+            .ok_or(OrderError::Overflow)?;
+        let high = self.order[span.end - 1]
+            .checked_add(delta)
+            .ok_or(OrderError::Overflow)?;
+        let below = span.start.checked_sub(1).map(|index| self.order[index]);
+        let above = self.order.get(span.end).copied();
+        if below.is_some_and(|below| below > low) || above.is_some_and(|above| above < high) {
+            return Err(OrderError::Unsorted);
+        }
+        for relative in &mut orders_mut(&mut self.order)[span] {
+            *relative += delta;
+        }
+        Ok(())
+    }
+
+    /// Count the elements with an order in `range` in `O(log n)`.
+    pub fn count_in_range<R>(&self, range: R) -> usize
+    where
+        R: RangeBounds<isize>,
+    {
+        self.span_by_order(range).len()
+    }
+
+    /// Remove and return the element with the highest order if `f` accepts it.
+    pub fn pop_max_if<F>(&mut self, f: F) -> Option<(isize, T)>
+    where
+        F: FnOnce(isize, &T) -> bool,
+    {
+        if f(*self.order.last()?, self.items.last()?) {
+            self.pop().map(|(item, relative)| (relative, item))
+        } else {
+            None
+        }
+    }
+
+    /// Remove and return the element with the lowest order if `f` accepts it.
+    pub fn pop_min_if<F>(&mut self, f: F) -> Option<(isize, T)>
+    where
+        F: FnOnce(isize, &T) -> bool,
+    {
+        if f(*self.order.first()?, self.items.first()?) {
+            let (item, relative) = self.remove(0);
+            Some((relative, item))
+        } else {
+            None
+        }
+    }
+
+    /// Keep only the `n` elements with the highest orders, returning the removed pairs.
+    ///
+    /// The removed pairs are in ascending order.
+    pub fn retain_top_n(&mut self, n: usize) -> Vec<(isize, T)> {
+        let cut = self.len().saturating_sub(n);
+        self.drain_pairs(0..cut)
+    }
+
+    /// Keep only the `n` elements with the lowest orders, returning the removed pairs.
+    ///
+    /// The removed pairs are in ascending order.
+    pub fn retain_bottom_n(&mut self, n: usize) -> Vec<(isize, T)> {
+        let cut = n.min(self.len());
+        self.drain_pairs(cut..self.len())
+    }
+
+    /// Insert a value like [IsizeVec::insert], also returning the order of its new left neighbor.
+    ///
+    /// The neighbor order is `None` if the value was inserted at the front.
+    pub fn insert_with_left_order(&mut self, relative: isize, item: T) -> (usize, Option<isize>) {
+        let index = self.insert(relative, item);
+        let left = index.checked_sub(1).map(|left| self.order[left]);
+        (index, left)
+    }
+
+    /// Insert a marker produced by `f(a, b)` between every two adjacent distinct orders `a < b`.
+    ///
+    /// This increases the length of the vector. Each marker gets an order strictly between `a` and
+    /// `b` if one exists, and is otherwise placed at order `b` in front of the elements there.
+    pub fn intersperse_boundaries<F>(&mut self, mut f: F)
+    where
+        F: FnMut(isize, isize) -> T,
+    {
+        let boundaries = self.order_gaps().count();
+        if boundaries == 0 {
+            return;
+        }
+        self.reserve(boundaries);
+        let start = self.upper_bound(self.order[0]);
+        let order = orders_mut(&mut self.order);
+        let moved = order
+            .drain(start..)
+            .zip(self.items.drain(start..))
+            .collect::<Vec<_>>();
+        for (relative, item) in moved {
+            if let Some(&last) = order.last() {
+                if last != relative {
+                    emit(&mut self.on_change, ChangeEvent::Inserted(order.len()));
+                    order.push(midpoint(last, relative).unwrap_or(relative));
+                    self.items.push(f(last, relative));
+                }
+            }
+            order.push(relative);
+            self.items.push(item);
+        }
+    }
+
+    /// Change the order of every element at order `from` to `to`, moving them to their sorted
+    /// position.
+    ///
+    /// The moved elements keep their relative position, and are placed among existing elements of
+    /// order `to` according to the tie-break policy, like [IsizeVec::set_order].
+    pub fn replace_order(&mut self, from: isize, to: isize) {
+        let span = self.indices_of_order(from);
+        if span.is_empty() || from == to {
+            return;
+        }
+        let appends = self.tiebreak.appends(to);
+        let before = |&x: &isize| x < to || (appends && x == to);
+        let order = orders_mut(&mut self.order);
+        for relative in &mut order[span.clone()] {
+            *relative = to;
+        }
+        if to > from {
+            let end = span.end + order[span.end..].partition_point(before);
+            self.items[span.start..end].rotate_left(span.len());
+            order[span.start..end].rotate_left(span.len());
+        } else {
+            let start = order[..span.start].partition_point(before);
+            self.items[start..span.end].rotate_right(span.len());
+            order[start..span.end].rotate_right(span.len());
+        }
+    }
+
+    /// Consume this vector, mapping each `(order, item)` pair through `f` in ascending order.
+    pub fn into_items_with<U, F>(self, mut f: F) -> Vec<U>
+    where
+        F: FnMut(isize, T) -> U,
+    {
+        into_orders(self.order)
+            .into_iter()
+            .zip(self.items)
+            .map(|(relative, item)| f(relative, item))
+            .collect()
+    }
+
+    /// Remove and return up to `page_size` elements with the lowest orders, in ascending order.
+    ///
+    /// Calling this repeatedly pages through the whole vector by order.
+    pub fn drain_page(&mut self, page_size: usize) -> Vec<(isize, T)> {
+        let end = page_size.min(self.len());
+        self.drain_pairs(0..end)
+    }
+
+    /// Map every distinct order to the range of indices holding it, in one `O(n)` scan.
+    pub fn order_index(&self) -> BTreeMap<isize, Range<usize>> {
+        let mut index = BTreeMap::new();
+        let mut start = 0;
+        for (end, pair) in self.order.windows(2).enumerate() {
+            if pair[0] != pair[1] {
+                index.insert(pair[0], start..end + 1);
+                start = end + 1;
+            }
+        }
+        if let Some(&last) = self.order.last() {
+            index.insert(last, start..self.order.len());
+        }
+        index
+    }
+
+    /// Pick an index with probability proportional to its order, given a random `rng_value` in
+    /// `[0, 1)`.
+    ///
+    /// Negative orders weigh zero. Returns `None` if `rng_value` is outside `[0, 1)` or no order is
+    /// positive.
+    pub fn weighted_index(&self, rng_value: f64) -> Option<usize> {
+        let weight = |relative: isize| relative.max(0) as f64;
+        let total: f64 = self.order.iter().map(|&relative| weight(relative)).sum();
+        if !(0.0..1.0).contains(&rng_value) || total <= 0.0 {
+            return None;
+        }
+        let target = rng_value * total;
+        let mut cumulative = 0.0;
+        for (index, &relative) in self.order.iter().enumerate() {
+            cumulative += weight(relative);
+            if relative > 0 && target < cumulative {
+                return Some(index);
+            }
+        }
+        self.order.iter().rposition(|&relative| relative > 0)
+    }
+
+    /// Collect `(order, &item)` pairs into a vector.
+    ///
+    /// This allocates the outer vector in `O(n)` but does not copy any item.
+    pub fn pair_refs(&self) -> Vec<(isize, &T)> {
+        self.iter_with_order().collect()
+    }
+
+    /// Check whether the distinct orders form a consecutive run starting at `0`.
+    ///
+    /// An empty vector is compacted.
+    pub fn is_compacted(&self) -> bool {
+        matches!(self.order.first(), None | Some(0))
+            && self
+                .order
+                .windows(2)
+                .all(|pair| pair[1] == pair[0] || pair[1] == pair[0] + 1)
+    }
+
+    /// Renumber the orders to `0, 1, 2, ...` per distinct order, keeping the layout.
+    ///
+    /// Does nothing if the vector [IsizeVec::is_compacted] already.
+    pub fn compact_orders(&mut self) {
+        if self.is_compacted() {
+            return;
+        }
+        let order = orders_mut(&mut self.order);
+        let mut previous = order[0];
+        let mut rank = 0;
+        for relative in order.iter_mut() {
+            if *relative != previous {
+                previous = *relative;
+                rank += 1;
+            }
+            *relative = rank;
+        }
+    }
+
+    /// Get an iterator over the items ordered below `threshold`.
+    pub fn take_while_order(&self, threshold: isize) -> Iter<'_, T> {
+        self.items[..self.lower_bound(threshold)].iter()
+    }
+
+    /// Get an iterator over the items ordered at or above `threshold`.
+    pub fn skip_while_order(&self, threshold: isize) -> Iter<'_, T> {
+        self.items[self.lower_bound(threshold)..].iter()
+    }
+
+    /// Insert a value at exactly `index` with order `relative`, skipping the search.
+    ///
+    /// Fails without changing anything if the value would not be sorted at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn insert_exact(
+        &mut self,
+        index: usize,
+        relative: isize,
+        item: T,
+    ) -> Result<(), OrderError> {
+        assert!(index <= self.len(), "insertion index is out of bounds");
+        let after_left = index == 0 || self.order[index - 1] <= relative;
+        let before_right = index == self.len() || relative <= self.order[index];
+        if !(after_left && before_right) {
+            return Err(OrderError::Unsorted);
+        }
+        self.insert_at(index, relative, item);
+        Ok(())
+    }
+}
+
+impl<T, I> Index<I> for IsizeVec<T>
+where
+    I: SliceIndex<[T]>,
+{
+    type Output = <I as SliceIndex<[T]>>::Output;
+    fn index(&self, index: I) -> &<Vec<T> as Index<I>>::Output {
+        &self.items[index]
+    }
+}
+
+impl<T, I> IndexMut<I> for IsizeVec<T>
+where
+    I: SliceIndex<[T]>,
+{
+    fn index_mut(&mut self, index: I) -> &mut <Vec<T> as Index<I>>::Output {
+        &mut self.items[index]
+    }
+}
+
+impl<T> TryFrom<(Vec<isize>, Vec<T>)> for IsizeVec<T> {
+    type Error = FromPartsError;
+
+    /// Build a vector from its orders and items, which must be of equal length with the orders
+    /// sorted from lowest to highest.
+    fn try_from((order, items): (Vec<isize>, Vec<T>)) -> Result<Self, FromPartsError> {
+        if order.len() != items.len() {
+            return Err(FromPartsError::LengthMismatch);
+        }
+        if order.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(FromPartsError::Unsorted);
+        }
+        let mut vector = IsizeVec::new();
+        vector.items = items;
+        vector.order = Orders::from(order);
+        Ok(vector)
+    }
+}
+
+impl<T> From<Vec<T>> for IsizeVec<T> {
+    /// Give each item its index as order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vector is longer than `isize::MAX`, which can only happen for zero-sized items.
+    fn from(items: Vec<T>) -> Self {
+        let order = (0..items.len())
+            .map(|index| isize::try_from(index).expect("index exceeds isize::MAX"))
+            .collect::<Vec<_>>();
+        Self {
+            items,
+            order: Orders::from(order),
+            ..Self::default()
+        }
+    }
+}
+
+impl<T> Extend<(isize, T)> for IsizeVec<T> {
+    /// Insert all pairs as by [IsizeVec::insert_batch].
+    fn extend<I: IntoIterator<Item = (isize, T)>>(&mut self, iter: I) {
+        self.insert_batch(iter);
+    }
+}
+
+impl<T> AddAssign<(isize, T)> for IsizeVec<T> {
+    /// Insert the pair as by [IsizeVec::insert].
+    fn add_assign(&mut self, (relative, item): (isize, T)) {
+        self.insert(relative, item);
+    }
+}
+
+impl<T> AddAssign<IsizeVec<T>> for IsizeVec<T> {
+    /// Merge all elements of `other` in a single pass.
+    ///
+    /// Elements of equal order keep their relative position from `other`, and are placed after or
+    /// before existing elements of that order according to the tie-break policy.
+    fn add_assign(&mut self, other: IsizeVec<T>) {
+        self.merge_sorted(into_orders(other.order).into_iter().zip(other.items));
+    }
+}
+
+impl<T> FromIterator<(isize, T)> for IsizeVec<T> {
+    fn from_iter<I: IntoIterator<Item = (isize, T)>>(iter: I) -> Self {
+        let mut vector = IsizeVec::new();
+        vector.insert_batch(iter);
+        vector
+    }
+}
+
+impl<T> IntoIterator for IsizeVec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IsizeVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.items.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut IsizeVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.items.iter_mut()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize, Serialize)]
+struct Columnar<O, I> {
+    orders: O,
+    items: I,
+}
+
+#[cfg(feature = "serde")]
+impl<T> IsizeVec<T> {
+    /// Serialize as a struct of two parallel arrays, `{ orders: [...], items: [...] }`.
+    ///
+    /// Use with `#[serde(serialize_with = "IsizeVec::serialize_columnar")]`.
+    pub fn serialize_columnar<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        Columnar {
+            orders: &self.order[..],
+            items: &self.items,
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserialize from the format written by [IsizeVec::serialize_columnar].
+    ///
+    /// Fails if the arrays differ in length or the orders are not sorted.
+    pub fn deserialize_columnar<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Deserialize<'de>,
+    {
+        let columnar = Columnar::<Vec<isize>, Vec<T>>::deserialize(deserializer)?;
+        if columnar.orders.len() != columnar.items.len() {
+            return Err(D::Error::custom("orders and items differ in length"));
+        }
+        if columnar.orders.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(D::Error::custom("orders are not sorted"));
+        }
+        Ok(Self {
+            items: columnar.items,
+            order: Orders::from(columnar.orders),
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            on_change: None,
+        })
+    }
+}
+
+/// Draining iterator over the elements of an [IsizeVec], from the lowest order.
+///
+/// Created by [IsizeVec::drain_sorted]. The backing vectors are moved out of the vector while
+/// draining, and the remaining elements are moved back when the iterator is dropped. If the
+/// iterator is leaked the vector is left empty.
+pub struct DrainSorted<'a, T> {
+    vector: &'a mut IsizeVec<T>,
+    items: IntoIter<T>,
+    order: IntoIter<isize>,
+}
+
+impl<'a, T> DrainSorted<'a, T> {
+    fn new(vector: &'a mut IsizeVec<T>) -> Self {
+        let items = mem::take(&mut vector.items).into_iter();
+        let order = into_orders(mem::take(&mut vector.order)).into_iter();
+        Self {
+            vector,
+            items,
+            order,
+        }
+    }
+}
+
+impl<'a, T> Iterator for DrainSorted<'a, T> {
+    type Item = (isize, T);
+    fn next(&mut self) -> Option<(isize, T)> {
+        let pair = (self.order.next()?, self.items.next()?);
+        emit(&mut self.vector.on_change, ChangeEvent::Removed(0));
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for DrainSorted<'a, T> {}
+
+impl<'a, T> Drop for DrainSorted<'a, T> {
+    fn drop(&mut self) {
+        self.vector.items = mem::take(&mut self.items).collect();
+        self.vector.order = Orders::from(mem::take(&mut self.order).collect::<Vec<_>>());
+        self.vector.reserve(0);
+    }
+}
+
+/// Error for operations that would break the ordering of an [IsizeVec].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderError {
+    /// The elements would no longer be sorted by order.
+    Unsorted,
+    /// An order would overflow `isize`.
+    Overflow,
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Unsorted => f.write_str("elements would no longer be sorted by order"),
+            OrderError::Overflow => f.write_str("order would overflow"),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+/// Builder for configuring an [IsizeVec] before construction.
+///
+/// ```
+/// use isize_vec::{IsizeVecBuilder, Tiebreak};
+///
+/// let mut vector = IsizeVecBuilder::new()
+///     .capacity(16)
+///     .tiebreak(Tiebreak::Append)
+///     .build();
+///
+/// vector.insert(-1, 'a');
+/// vector.insert(-1, 'b');
+///
+/// assert_eq!(vector[0], 'a');
+/// ```
+#[derive(Clone, Debug)]
+pub struct IsizeVecBuilder<T> {
+    capacity: usize,
+    tiebreak: Tiebreak,
+    balanced: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T> Default for IsizeVecBuilder<T> {
+    fn default() -> Self {
+        Self {
+            capacity: 0,
+            tiebreak: Tiebreak::Sign,
+            balanced: false,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> IsizeVecBuilder<T> {
+    /// Create a new builder with no reserved capacity and [Tiebreak::Sign].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserve space for at least `capacity` elements.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Set the placement of inserted values relative to values of the same order.
+    pub fn tiebreak(mut self, tiebreak: Tiebreak) -> Self {
+        self.tiebreak = tiebreak;
+        self
+    }
+
+    /// Keep the capacities of both backing vectors equal whenever they grow.
+    ///
+    /// This avoids one vector reallocating while the other still has room, at the cost of
+    /// growing both vectors together.
+    pub fn balance_capacity(mut self, balanced: bool) -> Self {
+        self.balanced = balanced;
+        self
+    }
+
+    /// Build the vector.
+    pub fn build(self) -> IsizeVec<T> {
+        let mut vector = IsizeVec::with_capacity(self.capacity);
+        vector.tiebreak = self.tiebreak;
+        vector.balanced = self.balanced;
+        vector
+    }
+}
+
+/// Invariant violation reported by [IsizeVec::validate].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Invariant {
+    /// The items and orders differ in length.
+    LengthMismatch,
+    /// The order at `at` is greater than the order at `at + 1`.
+    NotSorted {
+        /// Index of the first element of the unsorted pair.
+        at: usize,
+    },
+}
+
+/// Find an order strictly between `low` and `high`, if there is one.
+fn midpoint(low: isize, high: isize) -> Option<isize> {
+    if high.checked_sub(low).map_or(high > low, |gap| gap >= 2) {
+        Some(((low as i128 + high as i128) / 2) as isize)
+    } else {
+        None
+    }
+}
+
+/// Error for building an [IsizeVec] from invalid parts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FromPartsError {
+    /// The orders and items differ in length.
+    LengthMismatch,
+    /// The orders are not sorted from lowest to highest.
+    Unsorted,
+}
+
+impl fmt::Display for FromPartsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromPartsError::LengthMismatch => f.write_str("orders and items differ in length"),
+            FromPartsError::Unsorted => f.write_str("orders are not sorted"),
+        }
+    }
+}
+
+impl std::error::Error for FromPartsError {}
+
+/// Cursor over an [IsizeVec] for stateful traversal with insertion.
+///
+/// Created by [IsizeVec::cursor]. The cursor points at an element, or past the end of the vector
+/// when its index equals the length.
+pub struct Cursor<'a, T> {
+    vector: &'a mut IsizeVec<T>,
+    index: usize,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Get the index the cursor points at.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the element the cursor points at, along with its order.
+    pub fn current(&self) -> Option<(isize, &T)> {
+        self.vector.nth_from_min(self.index)
+    }
+
+    /// Move the cursor to the next element, stopping past the end.
+    pub fn move_next(&mut self) {
+        if self.index < self.vector.len() {
+            self.index += 1;
+        }
+    }
+
+    /// Move the cursor to the previous element, stopping at the first.
+    pub fn move_prev(&mut self) {
+        self.index = self.index.saturating_sub(1);
+    }
+
+    /// Insert a value as by [IsizeVec::insert] and move the cursor to it.
+    ///
+    /// Returns the index of insertion.
+    pub fn insert_here(&mut self, relative: isize, item: T) -> usize {
+        self.index = self.vector.insert(relative, item);
+        self.index
+    }
+}
+
+/// Vector of items sorted by an associated `i32` order.
+///
+/// This is the narrow counterpart of [IsizeVec], created by [IsizeVec::to_i32_orders] for
+/// memory-sensitive storage, and converted back with [From]. It supports the core insertions and
+/// removals with the default sign tie-break; convert it to an [IsizeVec] for everything else.
+#[derive(Clone, Debug)]
+pub struct I32Vec<T> {
+    items: Vec<T>,
+    order: Vec<i32>,
+}
+
+impl<T> Default for I32Vec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> I32Vec<T> {
+    /// Create a new vector.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Insert an element into the vector.
+    ///
+    /// As with [IsizeVec::insert], an element is prepended to the elements of the same order if
+    /// `relative` is signed, and appended if unsigned.
+    ///
+    /// Returns the index of insertion.
+    pub fn insert(&mut self, relative: i32, item: T) -> usize {
+        let index = if relative >= 0 {
+            self.order.partition_point(|&x| x <= relative)
+        } else {
+            self.order.partition_point(|&x| x < relative)
+        };
+        self.items.insert(index, item);
+        self.order.insert(index, relative);
+        index
+    }
+
+    /// Remove the given index from the vector.
+    pub fn remove(&mut self, index: usize) -> (T, i32) {
+        (self.items.remove(index), self.order.remove(index))
+    }
+
+    /// Remove the last element from this vector.
+    pub fn pop(&mut self) -> Option<(T, i32)> {
+        Some((self.items.pop()?, self.order.pop()?))
+    }
+
+    /// Get an iterator to the values.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.items.iter()
+    }
+
+    /// Get an iterator to the order values, from lowest to highest.
+    #[inline]
+    pub fn iter_order(&self) -> Copied<Iter<'_, i32>> {
+        self.order.iter().copied()
+    }
+
+    /// Returns the length of the container
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the container is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the item at a given index.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.items.get(index)
+    }
+}
+
+impl<T> From<I32Vec<T>> for IsizeVec<T> {
+    fn from(vector: I32Vec<T>) -> Self {
+        let mut wide = IsizeVec::new();
+        wide.items = vector.items;
+        wide.order = Orders::from(
+            vector
+                .order
+                .into_iter()
+                .map(|relative| relative as isize)
+                .collect::<Vec<_>>(),
+        );
+        wide
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IsizeVec;
+    use std::ops::Bound;
+
+    #[quickcheck_macros::quickcheck]
+    fn inserting_appends_or_prepends(relative: isize, values: usize) {
+        let mut vector = IsizeVec::new();
+
+        for value in 0..values {
+            vector.insert(relative, value);
+        }
+
+        if relative >= 0 {
+            let mut value = 0;
+            for item in vector.iter() {
+                assert_eq!(value, *item);
+                value += 1;
+            }
+            assert_eq!(value, values);
+        } else {
+            let mut value = values;
+            for item in vector.iter() {
+                value -= 1;
+                assert_eq!(value, *item);
+            }
+            assert_eq!(value, 0);
+        }
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn insertion_behaves_as_ordering(orders: Vec<(u8, isize)>) {
+        let mut vector = IsizeVec::new();
+
+        for (item, order) in &orders {
+            vector.insert(*order, item);
+        }
+
+        let mut unsigned = orders
+            .iter()
+            .cloned()
+            .filter(|(_, o)| o >= &0)
+            .collect::<Vec<_>>();
+
+        let mut signed = orders
+            .iter()
+            .cloned()
+            .filter(|(_, o)| o < &0)
+            .collect::<Vec<_>>();
+
+        unsigned.sort_by_key(|a| a.1);
+        signed.reverse();
+        signed.sort_by_key(|a| a.1);
+
+        for (idx, (item, _)) in signed.iter().chain(unsigned.iter()).enumerate() {
+            assert_eq!(vector[idx], item);
+        }
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn first_right_of_size(orders: Vec<isize>) {
+        let mut vector = IsizeVec::new();
+
+        for order in &orders {
+            vector.insert(*order, ());
+        }
+
+        assert_eq!(vector.first_right_of(isize::MAX), vector.len());
+    }
+
+    #[test]
+    fn basic() {
+        let mut vector = IsizeVec::new();
+
+        let value = 0;
+        vector.insert(0, 0);
+        vector.insert(1, 1);
+
+        let mut number = 0;
+        for item in vector.iter() {
+            assert_eq!(number, *item);
+            number += 1;
+        }
+        assert_eq!(number, 2);
+
+        vector.retain(|&x| x != value);
+
+        let mut number = 1;
+        for item in vector.iter() {
+            assert_eq!(number, *item);
+            number += 1;
+        }
+        assert_eq!(number, 2);
+    }
+
+    #[test]
+    fn haystack() {
+        let mut vector = IsizeVec::new();
 
         let value = 0;
+        for _ in 0..10 {
+            vector.insert(0, value);
+        }
+        for _ in 0..10 {
+            vector.insert(2, value);
+        }
+
+        vector.insert(1, 1);
+
+        vector.retain(|&x| x != value);
+
+        let mut count = 0;
+        for item in vector.iter() {
+            assert_eq!(*item, 1);
+            count += 1;
+        }
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn find_first_positive() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(0, 'b');
+        vector.insert(1, 'c');
+
+        assert!(matches!(vector.first_positive(), 1));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(-1, 'b');
+
+        assert!(matches!(vector.first_positive(), 2));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(0, 'b');
+
+        assert!(matches!(vector.first_positive(), 1));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+
+        assert!(matches!(vector.first_positive(), 0));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(0, 'b');
+        vector.insert(0, 'c');
+
+        assert!(matches!(vector.first_positive(), 0));
+    }
+
+    #[test]
+    fn find_first_right_of() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'a');
+
+        assert!(matches!(vector.first_right_of(1), 2));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'a');
+        vector.insert(2, 'a');
+
+        assert!(matches!(vector.first_right_of(1), 2));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(2, 'a');
+
+        assert!(matches!(vector.first_right_of(1), 1));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-101, 'a');
+        vector.insert(-100, 'b');
+
+        assert!(matches!(vector.first_right_of(0), 2));
+        assert!(matches!(vector.first_right_of(-100), 2));
+        assert!(matches!(vector.first_right_of(-101), 1));
+        assert!(matches!(vector.first_right_of(-1000), 0));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(0, 'b');
+
+        assert!(matches!(vector.first_right_of(0), 2));
+    }
+
+    #[test]
+    fn split_at_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(0, 'b');
+        vector.insert(2, 'c');
+        vector.insert(2, 'd');
+
+        let (lower, upper) = vector.clone().split_at_order(-10);
+        assert!(lower.is_empty());
+        assert_eq!(upper.iter().collect::<String>(), "abcd");
+
+        let (lower, upper) = vector.clone().split_at_order(10);
+        assert_eq!(lower.iter().collect::<String>(), "abcd");
+        assert!(upper.is_empty());
+
+        let (lower, upper) = vector.split_at_order(2);
+        assert_eq!(lower.iter().collect::<String>(), "ab");
+        assert_eq!(upper.iter().collect::<String>(), "cd");
+        assert_eq!(upper.first_right_of(1), 0);
+    }
+
+    #[test]
+    fn merge_keep_last() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(5, 'b');
+
+        vector.merge_keep_last(vec![(5, 'c'), (-1, 'd'), (0, 'e'), (3, 'f'), (-1, 'g')]);
+
+        assert_eq!(vector.iter().collect::<String>(), "gefc");
+        assert_eq!(vector.first_right_of(-1), 1);
+        assert_eq!(vector.first_right_of(3), 3);
+        assert_eq!(vector.len(), 4);
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn iter_order_is_sorted(orders: Vec<isize>) {
+        let mut vector = IsizeVec::new();
+
+        for order in &orders {
+            vector.insert(*order, ());
+        }
+
+        let sequence = vector.iter_order().collect::<Vec<_>>();
+        assert_eq!(sequence.len(), vector.len());
+        assert!(sequence.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn insert_batch_behaves_as_insert(existing: Vec<(isize, u8)>, batch: Vec<(isize, u8)>) {
+        let mut expected = IsizeVec::new();
+        let mut vector = IsizeVec::new();
+
+        for (order, item) in &existing {
+            expected.insert(*order, *item);
+            vector.insert(*order, *item);
+        }
+        for (order, item) in &batch {
+            expected.insert(*order, *item);
+        }
+        vector.insert_batch(batch);
+
+        assert_eq!(
+            vector.iter_order().collect::<Vec<_>>(),
+            expected.iter_order().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vector.iter().collect::<Vec<_>>(),
+            expected.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn insert_batch_ties() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(1, 'b');
+
+        vector.insert_batch(vec![(1, 'c'), (-1, 'd'), (1, 'e'), (-1, 'f')]);
+
+        assert_eq!(vector.iter().collect::<String>(), "fdabce");
+    }
+
+    #[test]
+    fn checked_remove() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+
+        assert_eq!(vector.checked_remove(1), Ok(('b', 1)));
+        assert_eq!(vector.checked_remove(1), Err(1));
+        assert_eq!(vector.len(), 1);
+    }
+
+    #[test]
+    fn find_item_right_of() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+
+        assert_eq!(vector.item_right_of(1), None);
+        assert_eq!(vector.item_right_of(0), Some((1, &'b')));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(2, 'b');
+
+        assert_eq!(vector.item_right_of(1), Some((2, &'b')));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-101, 'a');
+        vector.insert(-100, 'b');
+
+        assert_eq!(vector.item_right_of(0), None);
+        assert_eq!(vector.item_right_of(-100), None);
+        assert_eq!(vector.item_right_of(-101), Some((-100, &'b')));
+        assert_eq!(vector.item_right_of(-1000), Some((-101, &'a')));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(0, 'b');
+
+        assert_eq!(vector.item_right_of(0), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn columnar_round_trip() {
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct Wrapper {
+            #[serde(
+                serialize_with = "IsizeVec::serialize_columnar",
+                deserialize_with = "IsizeVec::deserialize_columnar"
+            )]
+            vector: IsizeVec<char>,
+        }
+
+        let mut vector = IsizeVec::new();
+        vector.insert(5, 'a');
+        vector.insert(-3, 'b');
+        vector.insert(5, 'c');
+
+        let json = serde_json::to_string(&Wrapper { vector }).unwrap();
+        assert_eq!(
+            json,
+            r#"{"vector":{"orders":[-3,5,5],"items":["b","a","c"]}}"#
+        );
+
+        let wrapper: Wrapper = serde_json::from_str(&json).unwrap();
+        assert_eq!(wrapper.vector.iter().collect::<String>(), "bac");
+        assert_eq!(wrapper.vector.iter_order().collect::<Vec<_>>(), [-3, 5, 5]);
+
+        let mismatched = r#"{"vector":{"orders":[1,2],"items":["a"]}}"#;
+        assert!(serde_json::from_str::<Wrapper>(mismatched).is_err());
+
+        let unsorted = r#"{"vector":{"orders":[2,1],"items":["a","b"]}}"#;
+        assert!(serde_json::from_str::<Wrapper>(unsorted).is_err());
+    }
+
+    #[test]
+    fn prune() {
+        let mut vector = IsizeVec::<char>::new();
+        assert_eq!(vector.prune_below(0), 0);
+        assert_eq!(vector.prune_above(0), 0);
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(0, 'b');
+        vector.insert(0, 'c');
+        vector.insert(3, 'd');
+
+        assert_eq!(vector.prune_below(0), 1);
+        assert_eq!(vector.iter().collect::<String>(), "bcd");
+        assert_eq!(vector.prune_above(0), 1);
+        assert_eq!(vector.iter().collect::<String>(), "bc");
+        assert_eq!(vector.prune_above(-1), 2);
+        assert!(vector.is_empty());
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(3, 'b');
+
+        assert_eq!(vector.prune_below(4), 2);
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn reset_to_preserves_capacity() {
+        let mut vector = IsizeVec::new();
+        vector.insert_batch((0..100).map(|x| (x, x)));
+        let items = vector.items.capacity();
+        let order = vector.order.capacity();
+
+        vector.reset_to(vec![(3, 3), (1, 1)]);
+        assert_eq!(vector.iter().collect::<Vec<_>>(), [&1, &3]);
+        assert_eq!(vector.items.capacity(), items);
+        assert_eq!(vector.order.capacity(), order);
+
+        vector.reset_to((0..50).rev().map(|x| (x, x)));
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            (0..50).collect::<Vec<_>>()
+        );
+        assert_eq!(vector.items.capacity(), items);
+        assert_eq!(vector.order.capacity(), order);
+    }
+
+    #[test]
+    fn rank_of_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-5, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(1, 'd');
+        vector.insert(7, 'e');
+
+        assert_eq!(vector.rank_of_order(-100), 0);
+        assert_eq!(vector.rank_of_order(-5), 0);
+        assert_eq!(vector.rank_of_order(1), 1);
+        assert_eq!(vector.rank_of_order(2), 4);
+        assert_eq!(vector.rank_of_order(100), 5);
+    }
+
+    #[test]
+    fn nth_from_extremes() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(0, 'b');
+        vector.insert(4, 'c');
+
+        assert_eq!(vector.nth_from_min(0), Some((-2, &'a')));
+        assert_eq!(vector.nth_from_max(0), Some((4, &'c')));
+        assert_eq!(vector.nth_from_min(1), Some((0, &'b')));
+        assert_eq!(vector.nth_from_max(1), Some((0, &'b')));
+        assert_eq!(vector.nth_from_min(3), None);
+        assert_eq!(vector.nth_from_max(3), None);
+        assert_eq!(vector.nth_from_max(usize::MAX), None);
+    }
+
+    #[test]
+    fn range_by_order_mut() {
+        let mut vector = IsizeVec::new();
+        for order in -3..4 {
+            vector.insert(order, order);
+        }
+
+        for item in vector.range_by_order_mut(-1..=1) {
+            *item *= 10;
+        }
+
+        assert_eq!(
+            vector.iter().copied().collect::<Vec<_>>(),
+            [-3, -2, -10, 0, 10, 2, 3]
+        );
+        assert_eq!(vector.range_by_order(..-2), [-3]);
+        assert_eq!(vector.range_by_order(2..), [2, 3]);
+        assert!(vector
+            .range_by_order((Bound::Excluded(0), Bound::Excluded(1)))
+            .is_empty());
+        assert!(vector.range_by_order_mut(10..).is_empty());
+    }
+
+    #[test]
+    fn insert_or_get() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(2, 'b');
+
+        assert_eq!(vector.insert_or_get(2, 'c'), (1, Some('c')));
+        assert_eq!(vector.len(), 2);
+        assert_eq!(vector.insert_or_get(1, 'd'), (1, None));
+        assert_eq!(vector.iter().collect::<String>(), "adb");
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut vector = IsizeVec::new();
+        vector.insert(3, 'c');
+        vector.insert(-1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(5, 'd');
+
+        let mut drain = vector.drain_sorted();
+        assert_eq!(drain.size_hint(), (4, Some(4)));
+        assert_eq!(drain.next(), Some((-1, 'a')));
+        assert_eq!(drain.next(), Some((1, 'b')));
+        assert_eq!(drain.len(), 2);
+        drop(drain);
+
+        assert_eq!(vector.iter().collect::<String>(), "cd");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [3, 5]);
+
+        assert_eq!(
+            vector.drain_sorted().collect::<Vec<_>>(),
+            [(3, 'c'), (5, 'd')]
+        );
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn drain_sorted_leak_keeps_invariants() {
+        let mut vector = IsizeVec::new();
+        vector.insert(3, 'c');
+        vector.insert(-1, 'a');
+        vector.insert(1, 'b');
+
+        let mut drain = vector.drain_sorted();
+        assert_eq!(drain.next(), Some((-1, 'a')));
+        std::mem::forget(drain);
+
+        assert!(vector.validate().is_ok());
+        vector.insert(0, 'd');
+        assert_eq!(vector.items, ['d']);
+    }
+
+    #[test]
+    fn merge_all() {
+        let pairs = [
+            vec![(0, 'a'), (2, 'b'), (2, 'c'), (7, 'd')],
+            vec![(1, 'e'), (2, 'f'), (9, 'g')],
+            vec![(0, 'h'), (7, 'i')],
+        ];
+
+        let mut expected = IsizeVec::new();
+        let mut sources = Vec::new();
+        for source in &pairs {
+            let mut vector = IsizeVec::new();
+            for (order, item) in source {
+                vector.insert(*order, *item);
+                expected.insert(*order, *item);
+            }
+            sources.push(vector);
+        }
+
+        let merged = IsizeVec::merge_all(sources);
+        assert_eq!(
+            merged.iter().collect::<String>(),
+            expected.iter().collect::<String>()
+        );
+        assert_eq!(
+            merged.iter_order().collect::<Vec<_>>(),
+            expected.iter_order().collect::<Vec<_>>()
+        );
+        assert!(IsizeVec::<char>::merge_all(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn find_in_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(1, 'd');
+        vector.insert(2, 'c');
+
+        assert_eq!(vector.find_in_order(1, |&x| x == 'c'), Some(2));
+        assert_eq!(vector.find_in_order(1, |&x| x == 'a'), None);
+        assert_eq!(vector.find_in_order(2, |&x| x == 'c'), Some(4));
+        assert_eq!(vector.find_in_order(5, |_| true), None);
+    }
+
+    #[test]
+    fn clamp_order_gaps() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-100, 'a');
+        vector.insert(-98, 'b');
+        vector.insert(0, 'c');
+        vector.insert(0, 'd');
+        vector.insert(1, 'e');
+        vector.insert(isize::MAX, 'f');
+
+        vector.clamp_order_gaps(3);
+        assert_eq!(
+            vector.iter_order().collect::<Vec<_>>(),
+            [-100, -98, -95, -95, -94, -91]
+        );
+        assert_eq!(vector.iter().collect::<String>(), "abcdef");
+
+        let mut vector = IsizeVec::new();
+        vector.insert(isize::MIN, 'a');
+        vector.insert(isize::MAX, 'b');
+
+        vector.clamp_order_gaps(isize::MAX);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [isize::MIN, -1]);
+    }
+
+    #[test]
+    fn on_change_events() {
+        use super::ChangeEvent;
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+
+        let recorder = events.clone();
+        vector.on_change(Box::new(move |event| recorder.lock().unwrap().push(event)));
+
+        vector.insert(1, 'b');
+        vector.insert(-1, 'c');
+        vector.remove(1);
+        vector.clone().insert(0, 'd');
+        vector.clear();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                ChangeEvent::Inserted(1),
+                ChangeEvent::Inserted(0),
+                ChangeEvent::Removed(1),
+                ChangeEvent::Cleared,
+            ]
+        );
+    }
+
+    #[test]
+    fn on_change_mirrors_every_mutation() {
+        use super::ChangeEvent;
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let recorder = events.clone();
+        let mut vector = IsizeVec::new();
+        vector.on_change(Box::new(move |event| recorder.lock().unwrap().push(event)));
+
+        let mut mirror: Vec<Option<i32>> = Vec::new();
+        let mut check = |vector: &IsizeVec<i32>| {
+            for event in events.lock().unwrap().drain(..) {
+                match event {
+                    ChangeEvent::Inserted(index) => mirror.insert(index, None),
+                    ChangeEvent::Removed(index) => {
+                        mirror.remove(index);
+                    }
+                    ChangeEvent::Cleared => mirror.clear(),
+                }
+            }
+            for (slot, &item) in mirror.iter_mut().zip(vector.iter()) {
+                assert_eq!(*slot.get_or_insert(item), item);
+            }
+            assert_eq!(mirror.len(), vector.len());
+        };
+
+        vector.push(9);
+        check(&vector);
+        vector.push_back(isize::MAX, 10).unwrap();
+        check(&vector);
+        vector.merge_keep_last(vec![(0, 0), (0, 1), (4, 4)]);
+        check(&vector);
+        vector.insert_batch(vec![(2, 2), (-1, -1), (6, 6), (3, 3)]);
+        check(&vector);
+        vector += (5, 5);
+        check(&vector);
+        vector.intersperse_boundaries(|a, _| a as i32 * 100);
+        check(&vector);
+        vector.retain(|&item| item < 100);
+        check(&vector);
+        vector.pop_max_if(|_, _| true);
+        check(&vector);
+        vector.pop_min_if(|_, _| true);
+        check(&vector);
+        vector.pop();
+        check(&vector);
+        vector.coalesce(|&left, &right| if right == left + 1 { Some(left) } else { None });
+        check(&vector);
+        vector.merge_presorted(vec![(1, 7), (1, 7), (2, 8), (7, 8)]);
+        check(&vector);
+        vector.dedup_items_keep_last();
+        check(&vector);
+        vector.dedup_items();
+        check(&vector);
+        let mut removed = Vec::new();
+        vector.retain_into(|&item| item != 7, &mut removed);
+        check(&vector);
+        vector.insert_batch((-5..15).map(|x| (x, x as i32)));
+        check(&vector);
+        vector.prune_below(-3);
+        check(&vector);
+        vector.prune_above(12);
+        check(&vector);
+        vector.keep_order_band(-2..11);
+        check(&vector);
+        vector.trim_to_range(-1..10);
+        check(&vector);
+        vector.retain_top_n(12);
+        check(&vector);
+        vector.retain_bottom_n(10);
+        check(&vector);
+        vector.drain_page(2);
+        check(&vector);
+        vector.drain(1..3);
+        check(&vector);
+        vector.remove_all_with_order(5);
+        check(&vector);
+        vector.drain_sorted().next();
+        check(&vector);
+        vector.drain_sorted_rev().next();
+        check(&vector);
+        vector.split_off_by_order(7);
+        check(&vector);
+        vector.extract();
+        check(&vector);
+        vector.reset_to(vec![(1, 1), (0, 0)]);
+        check(&vector);
+    }
+
+    #[test]
+    fn try_reserve() {
+        use super::IsizeVecBuilder;
+
+        let mut vector = IsizeVec::<u64>::new();
         vector.insert(0, 0);
-        vector.insert(1, 1);
 
-        let mut number = 0;
-        for item in vector.iter() {
-            assert_eq!(number, *item);
-            number += 1;
+        assert!(vector.try_reserve(100).is_ok());
+        assert!(vector.items.capacity() >= 101);
+        assert!(vector.order.capacity() >= 101);
+
+        assert!(vector.try_reserve(usize::MAX).is_err());
+        assert!(vector.items.capacity() >= 101);
+        assert_eq!(vector.len(), 1);
+
+        let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+        vector.insert(0, 0u64);
+        assert!(vector.try_reserve(usize::MAX).is_err());
+        assert_eq!(vector.len(), 1);
+        assert!(vector.balanced_capacity());
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn reserve_balanced_overflow() {
+        use super::IsizeVecBuilder;
+
+        let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+        vector.insert(0, 0u64);
+        vector.reserve(usize::MAX);
+    }
+
+    #[test]
+    fn keep_order_band() {
+        let mut vector = IsizeVec::new();
+        for order in -3..4 {
+            vector.insert(order, order);
+        }
+
+        let mut band = vector.clone();
+        band.keep_order_band(-1..2);
+        assert_eq!(band.iter().copied().collect::<Vec<_>>(), [-1, 0, 1]);
+
+        let mut band = vector.clone();
+        band.keep_order_band(10..20);
+        assert!(band.is_empty());
+
+        let mut band = vector.clone();
+        band.keep_order_band(isize::MIN..isize::MAX);
+        assert_eq!(band.len(), vector.len());
+    }
+
+    #[test]
+    fn push_back() {
+        use super::OrderError;
+
+        let mut vector = IsizeVec::new();
+        assert_eq!(vector.max_order(), None);
+        assert_eq!(vector.push_back(-5, 'a'), Ok(0));
+        assert_eq!(vector.push_back(3, 'b'), Ok(1));
+        assert_eq!(vector.push_back(3, 'c'), Ok(2));
+        assert_eq!(vector.max_order(), Some(3));
+
+        assert_eq!(vector.push_back(2, 'd'), Err(OrderError::Unsorted));
+        assert_eq!(vector.iter().collect::<String>(), "abc");
+    }
+
+    #[test]
+    fn memory_usage() {
+        let mut vector = IsizeVec::<u32>::new();
+        assert_eq!(vector.memory_usage(), 0);
+
+        vector.insert(0, 0);
+        let usage = vector.memory_usage();
+        assert!(usage >= 4 + 8);
+
+        vector.reserve(100);
+        assert!(vector.memory_usage() > usage);
+        assert!(vector.memory_usage() >= 101 * (4 + 8));
+    }
+
+    #[test]
+    fn dedup_items() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'a');
+        vector.insert(2, 'b');
+        vector.insert(3, 'a');
+        vector.insert(4, 'c');
+        vector.insert(5, 'c');
+
+        vector.dedup_items();
+        assert_eq!(vector.iter().collect::<String>(), "abac");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 2, 3, 4]);
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, (1, 'a'));
+        vector.insert(1, (1, 'b'));
+        vector.insert(2, (2, 'c'));
+        vector.insert(3, (1, 'd'));
+
+        vector.dedup_items_by_key(|item| item.0);
+        assert_eq!(vector.iter().map(|item| item.1).collect::<String>(), "acd");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 2, 3]);
+    }
+
+    #[test]
+    fn order_of() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-4, 'a');
+        vector.insert(2, 'b');
+        vector.insert(7, 'a');
+
+        assert_eq!(vector.order_of(&'b'), Some(2));
+        assert_eq!(vector.order_of(&'c'), None);
+        assert_eq!(vector.order_of(&'a'), Some(-4));
+    }
+
+    #[test]
+    fn builder() {
+        use super::{IsizeVecBuilder, Tiebreak};
+
+        let pairs = [(-1, 'a'), (-1, 'b'), (1, 'c'), (1, 'd')];
+        for &capacity in &[0, 10] {
+            for &(tiebreak, expected) in &[
+                (Tiebreak::Sign, "bacd"),
+                (Tiebreak::Append, "abcd"),
+                (Tiebreak::Prepend, "badc"),
+            ] {
+                let builder = IsizeVecBuilder::new().capacity(capacity).tiebreak(tiebreak);
+
+                let mut vector = builder.clone().build();
+                assert!(vector.items.capacity() >= capacity);
+                assert!(vector.order.capacity() >= capacity);
+                for (order, item) in &pairs {
+                    vector.insert(*order, *item);
+                }
+                assert_eq!(vector.iter().collect::<String>(), expected);
+
+                let mut vector = builder.build();
+                vector.insert(1, 'x');
+                vector.insert_batch(pairs.iter().copied());
+                vector.remove(vector.iter().position(|&x| x == 'x').unwrap());
+                assert_eq!(vector.iter().collect::<String>(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let mut vector = IsizeVec::new();
+        vector.insert(3, 'c');
+        vector.insert(-2, 'a');
+        vector.insert(0, 'b');
+
+        assert_eq!(vector.into_sorted_vec(), ['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn comparison_boundaries() {
+        let mut vector = IsizeVec::new();
+        vector.insert(1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(3, 'c');
+        vector.insert(5, 'd');
+        vector.insert(5, 'e');
+
+        assert_eq!(vector.first_ge(1), 0);
+        assert_eq!(vector.first_gt(1), 2);
+        assert_eq!(vector.last_le(1), Some(1));
+        assert_eq!(vector.last_lt(1), None);
+
+        assert_eq!(vector.first_ge(5), 3);
+        assert_eq!(vector.first_gt(5), 5);
+        assert_eq!(vector.last_le(5), Some(4));
+        assert_eq!(vector.last_lt(5), Some(2));
+
+        assert_eq!(vector.first_ge(4), 3);
+        assert_eq!(vector.first_gt(4), 3);
+        assert_eq!(vector.last_le(4), Some(2));
+        assert_eq!(vector.last_lt(4), Some(2));
+
+        assert_eq!(vector.last_le(0), None);
+        assert_eq!(vector.first_ge(6), 5);
+
+        for relative in 0..7 {
+            assert_eq!(vector.first_gt(relative), vector.first_right_of(relative));
+        }
+    }
+
+    #[test]
+    fn swap() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(2, 'c');
+
+        vector.swap_items_only(0, 2);
+        assert_eq!(vector.iter().collect::<String>(), "cba");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1, 2]);
+
+        vector.swap(0, 1);
+        assert_eq!(vector.iter().collect::<String>(), "bca");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [1, 0, 2]);
+    }
+
+    #[test]
+    fn insert_if_absent() {
+        let mut vector = IsizeVec::new();
+        vector.insert(1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(2, 'c');
+
+        assert_eq!(vector.insert_if_absent(1, 'b'), None);
+        assert_eq!(vector.insert_if_absent(1, 'c'), Some(2));
+        assert_eq!(vector.insert_if_absent(2, 'c'), None);
+        assert_eq!(vector.iter().collect::<String>(), "abcc");
+    }
+
+    #[test]
+    fn drain_sorted_rev() {
+        let mut vector = IsizeVec::new();
+        vector.insert(3, 'c');
+        vector.insert(-1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(5, 'd');
+
+        assert_eq!(
+            vector.drain_sorted_rev().take(2).collect::<Vec<_>>(),
+            [(5, 'd'), (3, 'c')]
+        );
+        assert_eq!(vector.iter().collect::<String>(), "ab");
+
+        assert_eq!(
+            vector.drain_sorted_rev().collect::<Vec<_>>(),
+            [(1, 'b'), (-1, 'a')]
+        );
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn grouped() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(0, 'b');
+        vector.insert(-3, 'c');
+        vector.insert(4, 'd');
+        vector.insert(4, 'e');
+
+        let groups = vector.grouped();
+        assert_eq!(
+            groups,
+            [
+                (-3, vec![&'c']),
+                (0, vec![&'a', &'b']),
+                (4, vec![&'d', &'e'])
+            ]
+        );
+        assert!(groups
+            .into_iter()
+            .flat_map(|(_, group)| group)
+            .eq(vector.iter()));
+        assert!(IsizeVec::<char>::new().grouped().is_empty());
+    }
+
+    #[test]
+    fn validate() {
+        use super::Invariant;
+
+        let mut vector = IsizeVec::new();
+        for order in 0..5 {
+            vector.insert(order, order);
+        }
+        assert_eq!(vector.validate(), Ok(()));
+
+        vector.swap(1, 3);
+        assert_eq!(
+            vector.validate(),
+            Err(vec![
+                Invariant::NotSorted { at: 1 },
+                Invariant::NotSorted { at: 2 }
+            ])
+        );
+
+        super::orders_mut(&mut vector.order).pop();
+        assert_eq!(
+            vector.validate(),
+            Err(vec![
+                Invariant::LengthMismatch,
+                Invariant::NotSorted { at: 1 },
+                Invariant::NotSorted { at: 2 }
+            ])
+        );
+    }
+
+    #[test]
+    fn insert_after_item() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(0, 'b');
+        vector.insert(10, 'c');
+        vector.insert(11, 'd');
+
+        assert_eq!(vector.insert_after_item(|&x| x == 'a', 'e'), Some(2));
+        assert_eq!(vector.insert_after_item(|&x| x == 'c', 'f'), Some(4));
+        assert_eq!(vector.insert_after_item(|&x| x == 'd', 'g'), Some(6));
+        assert_eq!(vector.insert_after_item(|&x| x == 'z', 'h'), None);
+
+        assert_eq!(vector.iter().collect::<String>(), "abecfdg");
+        assert_eq!(
+            vector.iter_order().collect::<Vec<_>>(),
+            [0, 0, 5, 10, 10, 11, 12]
+        );
+
+        let mut vector = IsizeVec::new();
+        vector.insert(isize::MAX, 'a');
+        assert_eq!(vector.insert_after_item(|_| true, 'b'), Some(1));
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [isize::MAX; 2]);
+    }
+
+    #[test]
+    fn set_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(2, 'c');
+        vector.insert(3, 'd');
+
+        assert_eq!(vector.set_order(0, 2), 2);
+        assert_eq!(vector.iter().collect::<String>(), "bcad");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [1, 2, 2, 3]);
+
+        assert_eq!(vector.set_order(3, -1), 0);
+        assert_eq!(vector.iter().collect::<String>(), "dbca");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 1, 2, 2]);
+
+        assert_eq!(vector.set_order(1, 1), 1);
+        assert_eq!(vector.set_order(1, 0), 1);
+        assert_eq!(vector.iter().collect::<String>(), "dbca");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 0, 2, 2]);
+
+        assert_eq!(vector.set_order(0, 2), 3);
+        assert_eq!(vector.iter().collect::<String>(), "bcad");
+        assert_eq!(vector.validate(), Ok(()));
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn empty_boundaries(relative: isize) {
+        let vector = IsizeVec::<()>::new();
+
+        assert_eq!(vector.first_right_of(relative), 0);
+        assert_eq!(vector.first_positive(), 0);
+    }
+
+    #[test]
+    fn iter_mut_with_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(2, 10);
+        vector.insert(-1, 20);
+        vector.insert(3, 30);
+
+        for (order, item) in vector.iter_mut_with_order() {
+            *item *= order;
+        }
+
+        assert_eq!(
+            vector.iter_with_order().collect::<Vec<_>>(),
+            [(-1, &-20), (2, &20), (3, &90)]
+        );
+    }
+
+    #[test]
+    fn partition() {
+        let mut vector = IsizeVec::new();
+        for value in 0..10 {
+            vector.insert(10 - value, value);
+        }
+
+        let (even, odd) = vector.partition(|x| x % 2 == 0);
+        assert_eq!(even.iter().copied().collect::<Vec<_>>(), [8, 6, 4, 2, 0]);
+        assert_eq!(odd.iter().copied().collect::<Vec<_>>(), [9, 7, 5, 3, 1]);
+        assert_eq!(even.iter_order().collect::<Vec<_>>(), [2, 4, 6, 8, 10]);
+        assert_eq!(odd.iter_order().collect::<Vec<_>>(), [1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn collect_allocates_once() {
+        let vector = (0..1000).rev().map(|x| (x, x)).collect::<IsizeVec<_>>();
+        assert!(vector.iter().copied().eq(0..1000));
+        assert!((1000..2000).contains(&vector.items.capacity()));
+        assert!((1000..2000).contains(&vector.order.capacity()));
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 0);
+        vector.extend((1..1000).map(|x| (x, x)));
+        assert!(vector.iter().copied().eq(0..1000));
+        assert!((1000..2000).contains(&vector.items.capacity()));
+        assert!((1000..2000).contains(&vector.order.capacity()));
+
+        let mut vector = IsizeVec::with_capacity(10_000);
+        vector.insert(0, 0);
+        let capacities = (vector.items.capacity(), vector.order.capacity());
+        vector.extend(vec![(1, 1)]);
+        vector.reserve(5_000);
+        vector.extend((-500..-1).map(|x| (x, x)));
+        assert_eq!(
+            (vector.items.capacity(), vector.order.capacity()),
+            capacities
+        );
+        assert_eq!(vector.len(), 501);
+    }
+
+    #[test]
+    fn zip_by_order() {
+        let mut names = IsizeVec::new();
+        names.insert(0, 'a');
+        names.insert(2, 'b');
+        names.insert(3, 'c');
+        names.insert(3, 'd');
+        names.insert(7, 'e');
+
+        let mut sizes = IsizeVec::new();
+        sizes.insert(1, 10);
+        sizes.insert(2, 20);
+        sizes.insert(3, 30);
+        sizes.insert(8, 80);
+
+        assert_eq!(
+            names.zip_by_order(&sizes).collect::<Vec<_>>(),
+            [(2, &'b', &20), (3, &'c', &30)]
+        );
+        assert_eq!(names.zip_by_order(&IsizeVec::<()>::new()).count(), 0);
+    }
+
+    #[test]
+    fn next_order_after() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(4, 'd');
+
+        assert_eq!(vector.next_order_after(-2), Some(1));
+        assert_eq!(vector.next_order_after(1), Some(4));
+        assert_eq!(vector.next_order_after(4), None);
+        assert_eq!(vector.next_order_after(0), Some(1));
+        assert_eq!(vector.next_order_after(-10), Some(-2));
+    }
+
+    #[test]
+    fn prev_order_before() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(4, 'd');
+
+        assert_eq!(vector.prev_order_before(4), Some(1));
+        assert_eq!(vector.prev_order_before(1), Some(-2));
+        assert_eq!(vector.prev_order_before(-2), None);
+        assert_eq!(vector.prev_order_before(3), Some(1));
+        assert_eq!(vector.prev_order_before(10), Some(4));
+    }
+
+    #[test]
+    fn balance_capacity() {
+        use super::IsizeVecBuilder;
+
+        let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+        for value in 0..100u8 {
+            vector.insert(isize::from(value % 7), value);
+            assert!(vector.balanced_capacity());
+        }
+        vector.push(0);
+        vector.reserve(1000);
+        assert!(vector.balanced_capacity());
+        vector.insert_batch((0..5000).map(|x| (x, 0)));
+        assert!(vector.balanced_capacity());
+        assert!(vector.clone().balanced_capacity());
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 0u8);
+        assert!(!vector.balanced_capacity());
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn parts_are_paired(orders: Vec<isize>) {
+        let mut vector = IsizeVec::new();
+        for order in &orders {
+            vector.insert(*order, *order);
+        }
+
+        let (orders, items) = vector.parts();
+        assert_eq!(orders.len(), vector.len());
+        assert_eq!(items.len(), vector.len());
+        assert_eq!(orders, items);
+        assert!(orders.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn binary_search() {
+        let mut vector = IsizeVec::new();
+        for value in &[10, 20, 30, 40] {
+            vector.insert(*value as isize / 10, *value);
+        }
+
+        assert_eq!(vector.binary_search(&30), Ok(2));
+        assert_eq!(vector.binary_search(&25), Err(2));
+        assert_eq!(vector.binary_search(&50), Err(4));
+    }
+
+    #[test]
+    fn capacities() {
+        let mut vector = IsizeVec::<u16>::new();
+        assert_eq!(vector.items_capacity(), 0);
+        assert_eq!(vector.orders_capacity(), 0);
+
+        vector.reserve(50);
+        assert!(vector.items_capacity() >= 50);
+        assert!(vector.orders_capacity() >= 50);
+    }
+
+    #[test]
+    fn retain_indexed() {
+        let mut vector = IsizeVec::new();
+        for value in 0..7 {
+            vector.insert(value * 10, value);
+        }
+
+        vector.retain_indexed(|index, _| index % 2 == 1);
+        assert_eq!(vector.iter().copied().collect::<Vec<_>>(), [1, 3, 5]);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [10, 30, 50]);
+    }
+
+    #[test]
+    fn insert_balanced_long_run() {
+        let mut expected = IsizeVec::new();
+        let mut vector = IsizeVec::new();
+
+        for value in 0..5000 {
+            let relative = match value % 5 {
+                0 => -1,
+                1 => 3,
+                _ => 0,
+            };
+            assert_eq!(
+                vector.insert_balanced(relative, value),
+                expected.insert(relative, value)
+            );
+        }
+
+        assert!(vector.iter().eq(expected.iter()));
+        assert!(vector.iter_order().eq(expected.iter_order()));
+    }
+
+    #[test]
+    fn first_right_of_long_run() {
+        fn linear(order: &[isize], relative: isize) -> usize {
+            match order.binary_search(&relative) {
+                Ok(exact) => order[exact..]
+                    .iter()
+                    .position(|&x| x != relative)
+                    .map_or(order.len(), |index| exact + index),
+                Err(index) => index,
+            }
+        }
+
+        let mut vector = IsizeVec::new();
+        vector.insert_batch((0..100_000).map(|x| (if x < 10 { -1 } else { 0 }, x)));
+        vector.insert(1, 0);
+
+        for relative in -2..3 {
+            assert_eq!(
+                vector.first_right_of(relative),
+                linear(&vector.order, relative)
+            );
+        }
+        assert_eq!(vector.first_right_of(0), 100_000);
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn first_right_of_matches_linear_scan(orders: Vec<i8>, relative: i8) {
+        let mut vector = IsizeVec::new();
+        for order in &orders {
+            vector.insert(isize::from(*order), ());
+        }
+
+        let relative = isize::from(relative);
+        let expected = vector
+            .iter_order()
+            .position(|x| x > relative)
+            .unwrap_or(vector.len());
+        assert_eq!(vector.first_right_of(relative), expected);
+    }
+
+    #[test]
+    fn retain_large() {
+        let mut vector = IsizeVec::new();
+        vector.insert_batch((0..200_000).map(|x| (x / 3, x)));
+
+        vector.retain(|x| x % 4 == 0);
+        assert_eq!(vector.len(), 50_000);
+        for (order, item) in vector.iter_with_order() {
+            assert_eq!(order, item / 3);
+        }
+
+        vector.retain(|_| false);
+        assert!(vector.is_empty());
+        assert_eq!(vector.validate(), Ok(()));
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn retain_keeps_pairs(orders: Vec<isize>) {
+        let mut vector = IsizeVec::new();
+        for order in &orders {
+            vector.insert(*order, *order);
+        }
+
+        vector.retain(|x| x % 3 != 0);
+
+        let expected = vector.iter().copied().collect::<Vec<_>>();
+        assert!(vector.iter_order().eq(expected));
+        assert!(orders.iter().filter(|x| *x % 3 != 0).count() == vector.len());
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut expected = IsizeVec::new();
+        let mut vector = IsizeVec::new();
+        expected.insert(0, 'a');
+        vector.insert(0, 'a');
+
+        for item in &['b', 'c', 'd'] {
+            expected.push(*item);
+        }
+        vector.extend_from_slice(&['b', 'c', 'd']);
+
+        assert!(vector.iter().eq(expected.iter()));
+        assert!(vector.iter_order().eq(expected.iter_order()));
+        assert_eq!(vector.max_order(), Some(isize::MAX));
+    }
+
+    #[test]
+    fn split_first_last() {
+        let mut vector = IsizeVec::new();
+        assert_eq!(vector.split_first(), None);
+        assert_eq!(vector.split_last(), None);
+
+        vector.insert(4, 'a');
+        assert_eq!(vector.split_first(), Some(((4, &'a'), &[][..])));
+        assert_eq!(vector.split_last(), Some(((4, &'a'), &[][..])));
+
+        vector.insert(-1, 'b');
+        vector.insert(9, 'c');
+        assert_eq!(vector.split_first(), Some(((-1, &'b'), &['a', 'c'][..])));
+        assert_eq!(vector.split_last(), Some(((9, &'c'), &['b', 'a'][..])));
+    }
+
+    #[test]
+    fn clone_keeps_capacity_balanced() {
+        use super::IsizeVecBuilder;
+
+        let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+        for order in 0..5 {
+            vector.insert(order, order as u8);
+        }
+        assert!(vector.len() < vector.items.capacity());
+
+        let mut copy = vector.clone();
+        assert!(copy.balanced_capacity());
+        copy.insert(-1, 9);
+        assert!(copy.balanced_capacity());
+        assert!(vector.balanced_capacity());
+    }
+
+    #[cfg(feature = "cow-order")]
+    #[test]
+    fn cow_order_shares_until_mutation() {
+        use std::sync::Arc;
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+
+        let mut copy = vector.clone();
+        assert!(Arc::ptr_eq(&vector.order, &copy.order));
+
+        copy[0] = 'c';
+        assert!(Arc::ptr_eq(&vector.order, &copy.order));
+
+        copy.insert(-1, 'd');
+        assert!(!Arc::ptr_eq(&vector.order, &copy.order));
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(copy.iter_order().collect::<Vec<_>>(), [-1, 0, 1]);
+        assert_eq!(vector.iter().collect::<String>(), "ab");
+        assert_eq!(copy.iter().collect::<String>(), "dcb");
+
+        let shared = vector.clone();
+        assert_eq!(
+            vector
+                .partition(|_| true)
+                .0
+                .iter_order()
+                .collect::<Vec<_>>(),
+            [0, 1]
+        );
+        assert_eq!(shared.iter_order().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn iter_rev_with_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(2, 'b');
+        vector.insert(-1, 'a');
+        vector.insert(3, 'c');
+
+        assert_eq!(
+            vector.iter_rev_with_order().collect::<Vec<_>>(),
+            [(3, &'c'), (2, &'b'), (-1, &'a')]
+        );
+    }
+
+    #[test]
+    fn remove_all_with_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(2, 'd');
+
+        assert_eq!(vector.remove_all_with_order(1), ['b', 'c']);
+        assert_eq!(vector.remove_all_with_order(2), ['d']);
+        assert!(vector.remove_all_with_order(5).is_empty());
+        assert_eq!(vector.iter().collect::<String>(), "a");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn count_signs() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-3, 'a');
+        vector.insert(-1, 'b');
+        assert_eq!(
+            (vector.count_negative(), vector.count_nonnegative()),
+            (2, 0)
+        );
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(5, 'b');
+        assert_eq!(
+            (vector.count_negative(), vector.count_nonnegative()),
+            (0, 2)
+        );
+
+        vector.insert(-1, 'c');
+        assert_eq!(
+            (vector.count_negative(), vector.count_nonnegative()),
+            (1, 2)
+        );
+    }
+
+    #[test]
+    fn try_from_parts() {
+        use super::FromPartsError;
+        use std::convert::TryFrom;
+
+        let vector = IsizeVec::try_from((vec![-1, 2, 2], vec!['a', 'b', 'c'])).unwrap();
+        assert_eq!(vector.iter().collect::<String>(), "abc");
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 2, 2]);
+
+        assert_eq!(
+            IsizeVec::try_from((vec![1, 2], vec!['a'])).unwrap_err(),
+            FromPartsError::LengthMismatch
+        );
+        assert_eq!(
+            IsizeVec::try_from((vec![2, 1], vec!['a', 'b'])).unwrap_err(),
+            FromPartsError::Unsorted
+        );
+    }
+
+    #[test]
+    fn cursor() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(2, 'b');
+
+        let mut cursor = vector.cursor();
+        assert_eq!(cursor.current(), Some((0, &'a')));
+        cursor.move_prev();
+        assert_eq!(cursor.index(), 0);
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((2, &'b')));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), 2);
+        assert_eq!(cursor.current(), None);
+
+        assert_eq!(cursor.insert_here(1, 'c'), 1);
+        assert_eq!(cursor.current(), Some((1, &'c')));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some((2, &'b')));
+
+        assert_eq!(vector.iter().collect::<String>(), "acb");
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn to_i32_orders() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-5, 'a');
+        vector.insert(i32::MAX as isize, 'b');
+
+        let narrow = vector.to_i32_orders().unwrap();
+        assert_eq!(narrow.len(), 2);
+        assert_eq!(narrow.iter().collect::<String>(), "ab");
+        assert_eq!(narrow.iter_order().collect::<Vec<_>>(), [-5, i32::MAX]);
+
+        let wide = IsizeVec::from(narrow);
+        assert!(wide.iter_order().eq(vector.iter_order()));
+
+        vector.insert(i32::MIN as isize - 1, 'c');
+        assert!(vector.to_i32_orders().is_err());
+    }
+
+    #[test]
+    fn i32_vec_mutations() {
+        use super::I32Vec;
+
+        let mut narrow = I32Vec::new();
+        let mut wide = IsizeVec::new();
+        for (relative, item) in [(0, 'a'), (-1, 'b'), (0, 'c'), (-1, 'd'), (3, 'e')] {
+            assert_eq!(
+                narrow.insert(relative, item),
+                wide.insert(relative as isize, item)
+            );
+        }
+        assert_eq!(narrow.iter().collect::<String>(), "dbace");
+
+        assert_eq!(narrow.remove(1), ('b', -1));
+        assert_eq!(narrow.pop(), Some(('e', 3)));
+        assert_eq!(narrow.iter().collect::<String>(), "dac");
+        assert_eq!(narrow.iter_order().collect::<Vec<_>>(), [-1, 0, 0]);
+
+        narrow.pop();
+        narrow.pop();
+        narrow.pop();
+        assert_eq!(narrow.pop(), None);
+        assert!(narrow.is_empty());
+    }
+
+    #[test]
+    fn merge_presorted() {
+        let mut expected = IsizeVec::new();
+        let mut vector = IsizeVec::new();
+        for &(order, item) in &[(-4, 'a'), (0, 'b'), (3, 'c'), (9, 'd')] {
+            expected.insert(order, item);
+            vector.insert(order, item);
+        }
+
+        let batch = vec![
+            (-5, 'e'),
+            (-4, 'j'),
+            (-4, 'k'),
+            (0, 'f'),
+            (0, 'g'),
+            (4, 'h'),
+            (12, 'i'),
+        ];
+        for &(order, item) in &batch {
+            expected.insert(order, item);
+        }
+        vector.merge_presorted(batch);
+
+        assert!(vector.iter().eq(expected.iter()));
+        assert!(vector.iter_order().eq(expected.iter_order()));
+    }
+
+    #[test]
+    fn filter_map() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
+        vector.insert(0, 'b');
+        vector.insert(3, 'c');
+
+        let mapped = vector.filter_map(|order, item| {
+            if order >= 0 {
+                Some(item.to_string())
+            } else {
+                None
+            }
+        });
+        assert_eq!(mapped.iter().cloned().collect::<Vec<_>>(), ["b", "c"]);
+        assert_eq!(mapped.iter_order().collect::<Vec<_>>(), [0, 3]);
+    }
+
+    #[test]
+    fn order_gaps() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-3, 'a');
+        vector.insert(0, 'b');
+        vector.insert(0, 'c');
+        vector.insert(1, 'd');
+        vector.insert(10, 'e');
+
+        assert_eq!(
+            vector.order_gaps().collect::<Vec<_>>(),
+            [(-3, 0, 3), (0, 1, 1), (1, 10, 9)]
+        );
+        assert_eq!(IsizeVec::<char>::new().order_gaps().count(), 0);
+    }
+
+    #[test]
+    fn order_between() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(10, 'b');
+        vector.insert(11, 'c');
+        vector.insert(11, 'd');
+
+        assert_eq!(vector.order_between(0, 1), Some(5));
+        assert_eq!(vector.order_between(1, 2), None);
+        assert_eq!(vector.order_between(2, 3), None);
+        assert_eq!(vector.order_between(1, 0), None);
+        assert_eq!(vector.order_between(0, 4), None);
+
+        let mut vector = IsizeVec::new();
+        vector.insert(isize::MIN, 'a');
+        vector.insert(isize::MAX, 'b');
+        assert_eq!(vector.order_between(0, 1), Some(0));
+    }
+
+    #[test]
+    fn reassign_orders() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(2, 'c');
+        vector.insert(3, 'd');
+
+        vector.reassign_orders(&[3, 2, 1, 0]);
+        assert_eq!(vector.items, ['d', 'c', 'b', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1, 2, 3]);
+
+        vector.reassign_orders(&[5, 5, -1, 5]);
+        assert_eq!(vector.items, ['b', 'd', 'c', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 5, 5, 5]);
+        assert!(vector.validate().is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn reassign_orders_length_mismatch() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.reassign_orders(&[0, 1]);
+    }
+
+    #[test]
+    fn lower_and_upper_bound() {
+        let mut vector = IsizeVec::new();
+        assert_eq!(vector.lower_bound(0), 0);
+        assert_eq!(vector.upper_bound(0), 0);
+
+        vector.insert(-2, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(1, 'd');
+        vector.insert(4, 'e');
+
+        assert_eq!(vector.lower_bound(1), 1);
+        assert_eq!(vector.upper_bound(1), 4);
+        assert_eq!(vector.lower_bound(0), 1);
+        assert_eq!(vector.upper_bound(0), 1);
+        assert_eq!(vector.lower_bound(isize::MIN), 0);
+        assert_eq!(vector.upper_bound(isize::MIN), 0);
+        assert_eq!(vector.lower_bound(4), 4);
+        assert_eq!(vector.upper_bound(4), 5);
+        assert_eq!(vector.lower_bound(isize::MAX), 5);
+        assert_eq!(vector.upper_bound(isize::MAX), 5);
+
+        for relative in -4..7 {
+            assert_eq!(
+                vector.upper_bound(relative),
+                vector.first_right_of(relative)
+            );
+            assert_eq!(vector.lower_bound(relative), vector.first_left_of(relative));
+        }
+    }
+
+    #[test]
+    fn into_grouped() {
+        let mut vector = IsizeVec::new();
+        vector.insert(1, 'a');
+        vector.insert(-1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(3, 'd');
+        let flat = vector.iter().cloned().collect::<Vec<_>>();
+
+        let groups = vector.into_grouped();
+        assert_eq!(
+            groups,
+            [(-1, vec!['b']), (1, vec!['a', 'c']), (3, vec!['d'])]
+        );
+        assert_eq!(
+            groups
+                .into_iter()
+                .flat_map(|(_, items)| items)
+                .collect::<Vec<_>>(),
+            flat
+        );
+        assert!(IsizeVec::<char>::new().into_grouped().is_empty());
+    }
+
+    #[test]
+    fn swap_adjacent() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+
+        assert!(vector.swap_adjacent(0));
+        assert_eq!(vector.items, ['b', 'a', 'c']);
+        assert!(vector.swap_adjacent(1));
+        assert_eq!(vector.items, ['b', 'c', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1, 1]);
+        assert!(vector.validate().is_ok());
+
+        assert!(!vector.swap_adjacent(2));
+        assert!(!vector.swap_adjacent(usize::MAX));
+        assert_eq!(vector.items, ['b', 'c', 'a']);
+    }
+
+    #[test]
+    fn insertion_index() {
+        use super::{IsizeVecBuilder, Tiebreak};
+
+        for &tiebreak in &[Tiebreak::Sign, Tiebreak::Append, Tiebreak::Prepend] {
+            let mut vector = IsizeVecBuilder::new().tiebreak(tiebreak).build();
+            for &relative in &[0, -3, 5, -3, 0, 5, 0, -1, 2, -3, 5] {
+                let expected = vector.insertion_index(relative);
+                assert_eq!(vector.insert(relative, ()), expected);
+            }
+        }
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(-1, 'b');
+        vector.insert(0, 'c');
+        vector.insert(0, 'd');
+        assert_eq!(vector.insertion_index(-1), 0);
+        assert_eq!(vector.insertion_index(0), 4);
+        assert_eq!(vector.insertion_index(-5), 0);
+        assert_eq!(vector.insertion_index(7), 4);
+        assert_eq!(vector.len(), 4);
+    }
+
+    #[test]
+    fn into_iter_rev() {
+        let mut vector = IsizeVec::new();
+        vector.insert(2, 'a');
+        vector.insert(-1, 'b');
+        vector.insert(5, 'c');
+        vector.insert(2, 'd');
+
+        assert_eq!(
+            vector.into_iter_rev().collect::<Vec<_>>(),
+            [(5, 'c'), (2, 'd'), (2, 'a'), (-1, 'b')]
+        );
+    }
+
+    #[test]
+    fn first_unsorted_index() {
+        let mut vector = IsizeVec::new();
+        assert_eq!(vector.first_unsorted_index(), None);
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(3, 'd');
+        assert_eq!(vector.first_unsorted_index(), None);
+
+        vector.swap(1, 3);
+        assert_eq!(vector.first_unsorted_index(), Some(1));
+        vector.swap(0, 1);
+        assert_eq!(vector.first_unsorted_index(), Some(0));
+    }
+
+    #[test]
+    fn trim_to_range() {
+        let mut vector = IsizeVec::new();
+        for (relative, item) in (-3..4).zip("abcdefg".chars()) {
+            vector.insert(relative, item);
         }
-        assert_eq!(number, 2);
 
-        vector.retain(|&x| x != value);
+        let (below, above) = vector.trim_to_range(-1..2);
+        assert_eq!(below, ['a', 'b']);
+        assert_eq!(above, ['f', 'g']);
+        assert_eq!(vector.items, ['c', 'd', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 0, 1]);
 
-        let mut number = 1;
-        for item in vector.iter() {
-            assert_eq!(number, *item);
-            number += 1;
+        let (below, above) = vector.trim_to_range(5..9);
+        assert_eq!(below, ['c', 'd', 'e']);
+        assert!(above.is_empty());
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn fill_with() {
+        let mut next = 0;
+        let vector = IsizeVec::fill_with(3, 5, || {
+            next += 1;
+            next
+        });
+        assert_eq!(vector.items, [1, 2, 3, 4, 5]);
+        assert!(vector.iter_order().all(|relative| relative == 3));
+        assert_eq!(vector.iter_order().count(), 5);
+
+        assert!(IsizeVec::fill_with(3, 0, || 'a').is_empty());
+    }
+
+    #[test]
+    fn coalesce() {
+        let mut vector = IsizeVec::new();
+        for (relative, item) in [(0, 1), (1, 2), (2, 3), (3, 7), (4, 8), (5, 12)].iter() {
+            vector.insert(*relative, *item);
         }
-        assert_eq!(number, 2);
+
+        vector.coalesce(|&left, &right| if right - left == 1 { Some(right) } else { None });
+        assert_eq!(vector.items, [3, 8, 12]);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 3, 5]);
+
+        let mut empty = IsizeVec::<i32>::new();
+        empty.coalesce(|_, _| Some(0));
+        assert!(empty.is_empty());
     }
 
     #[test]
-    fn haystack() {
+    fn indices_of_order() {
         let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(2, 'b');
+        vector.insert(2, 'c');
+        vector.insert(2, 'd');
+        vector.insert(5, 'e');
 
-        let value = 0;
-        for _ in 0..10 {
-            vector.insert(0, value);
-        }
-        for _ in 0..10 {
-            vector.insert(2, value);
-        }
+        assert_eq!(vector.indices_of_order(0), 1..1);
+        assert_eq!(vector.indices_of_order(9), 5..5);
+        assert_eq!(vector.indices_of_order(-1), 0..1);
+        assert_eq!(vector.indices_of_order(2), 1..4);
+        assert_eq!(&vector[vector.indices_of_order(2)], ['b', 'c', 'd']);
+    }
 
-        vector.insert(1, 1);
+    #[test]
+    fn checked_swap() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'b');
 
-        vector.retain(|&x| x != value);
+        assert!(vector.checked_swap(0, 1));
+        assert_eq!(vector.items, ['b', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [1, 0]);
 
-        let mut count = 0;
-        for item in vector.iter() {
-            assert_eq!(*item, 1);
-            count += 1;
-        }
-        assert_eq!(count, 1);
+        assert!(!vector.checked_swap(2, 0));
+        assert!(!vector.checked_swap(0, 2));
+        assert_eq!(vector.items, ['b', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [1, 0]);
     }
 
     #[test]
-    fn find_first_positive() {
+    fn order_extent() {
         let mut vector = IsizeVec::new();
-        vector.insert(-1, 'a');
+        assert_eq!(vector.order_extent(), None);
+        assert_eq!(vector.min_order(), None);
+
+        vector.insert(4, 'a');
+        assert_eq!(vector.order_extent(), Some(4..=4));
+
+        vector.insert(-7, 'b');
+        vector.insert(12, 'c');
+        vector.insert(0, 'd');
+        assert_eq!(vector.min_order(), Some(-7));
+        assert_eq!(vector.order_extent(), Some(-7..=12));
+    }
+
+    #[test]
+    fn dedup_items_keep_last() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(1, 'a');
+        vector.insert(2, 'b');
+        vector.insert(3, 'a');
+        vector.insert(4, 'c');
+        vector.insert(5, 'c');
+        vector.insert(6, 'c');
+
+        vector.dedup_items_keep_last();
+        assert_eq!(vector.items, ['a', 'b', 'a', 'c']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [1, 2, 3, 6]);
+    }
+
+    #[test]
+    fn shift_orders_in_range() {
+        use super::OrderError;
+
+        let mut vector = IsizeVec::new();
+        vector.insert(0, 'a');
+        vector.insert(10, 'b');
+        vector.insert(12, 'c');
+        vector.insert(20, 'd');
+
+        assert_eq!(vector.shift_orders_in_range(10..13, 5), Ok(()));
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 15, 17, 20]);
+        assert_eq!(vector.shift_orders_in_range(15..18, -15), Ok(()));
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 0, 2, 20]);
+        assert_eq!(vector.shift_orders_in_range(30..40, 1), Ok(()));
+
+        assert_eq!(
+            vector.shift_orders_in_range(1..3, 19),
+            Err(OrderError::Unsorted)
+        );
+        assert_eq!(
+            vector.shift_orders_in_range(2..3, -3),
+            Err(OrderError::Unsorted)
+        );
+        assert_eq!(
+            vector.shift_orders_in_range(20..21, isize::MAX),
+            Err(OrderError::Overflow)
+        );
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 0, 2, 20]);
+        assert_eq!(vector.items, ['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn edit_orders() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-2, 'a');
         vector.insert(0, 'b');
         vector.insert(1, 'c');
+        vector.insert(1, 'd');
+        vector.insert(4, 'e');
 
-        assert!(matches!(vector.first_positive(), 1));
+        vector.edit_orders(|orders, _| {
+            for relative in orders {
+                *relative = -*relative;
+            }
+        });
+        assert_eq!(vector.items, ['e', 'c', 'd', 'b', 'a']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-4, -1, -1, 0, 2]);
+        assert!(vector.validate().is_ok());
+    }
 
+    #[test]
+    fn count_in_range() {
         let mut vector = IsizeVec::new();
-        vector.insert(-2, 'a');
-        vector.insert(-1, 'b');
+        for relative in &[-3, -1, 0, 0, 2, 5] {
+            vector.insert(*relative, ());
+        }
 
-        assert!(matches!(vector.first_positive(), 2));
+        assert_eq!(vector.count_in_range(-1..2), 3);
+        assert_eq!(vector.count_in_range(0..=2), 3);
+        assert_eq!(vector.count_in_range(..), vector.len());
+        assert_eq!(vector.count_in_range(3..5), 0);
+        assert_eq!(vector.count_in_range(6..), 0);
+        assert_eq!(IsizeVec::<()>::new().count_in_range(..), 0);
+    }
 
+    #[test]
+    fn pop_max_if_and_pop_min_if() {
         let mut vector = IsizeVec::new();
         vector.insert(-2, 'a');
-        vector.insert(0, 'b');
+        vector.insert(3, 'b');
+        vector.insert(7, 'c');
 
-        assert!(matches!(vector.first_positive(), 1));
+        assert_eq!(vector.pop_max_if(|relative, _| relative > 10), None);
+        assert_eq!(vector.pop_min_if(|_, &item| item == 'z'), None);
+        assert_eq!(vector.items, ['a', 'b', 'c']);
+
+        assert_eq!(
+            vector.pop_max_if(|relative, _| relative > 5),
+            Some((7, 'c'))
+        );
+        assert_eq!(vector.pop_min_if(|_, &item| item == 'a'), Some((-2, 'a')));
+        assert_eq!(vector.items, ['b']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [3]);
+
+        let mut empty = IsizeVec::<char>::new();
+        assert_eq!(empty.pop_max_if(|_, _| true), None);
+        assert_eq!(empty.pop_min_if(|_, _| true), None);
+    }
+
+    #[test]
+    fn retain_top_n() {
+        let mut vector = IsizeVec::new();
+        for (relative, item) in (0..5).zip("abcde".chars()) {
+            vector.insert(relative, item);
+        }
+
+        assert!(vector.retain_top_n(7).is_empty());
+        assert!(vector.retain_top_n(5).is_empty());
+        assert_eq!(vector.len(), 5);
+
+        assert_eq!(vector.retain_top_n(2), [(0, 'a'), (1, 'b'), (2, 'c')]);
+        assert_eq!(vector.items, ['d', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [3, 4]);
+    }
+
+    #[test]
+    fn retain_bottom_n() {
+        let mut vector = IsizeVec::new();
+        for (relative, item) in (0..5).zip("abcde".chars()) {
+            vector.insert(relative, item);
+        }
+
+        assert!(vector.retain_bottom_n(7).is_empty());
+        assert!(vector.retain_bottom_n(5).is_empty());
+        assert_eq!(vector.len(), 5);
+
+        assert_eq!(vector.retain_bottom_n(2), [(2, 'c'), (3, 'd'), (4, 'e')]);
+        assert_eq!(vector.items, ['a', 'b']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn insert_with_left_order() {
+        let mut vector = IsizeVec::new();
+        assert_eq!(vector.insert_with_left_order(5, 'a'), (0, None));
+        assert_eq!(vector.insert_with_left_order(-2, 'b'), (0, None));
+        assert_eq!(vector.insert_with_left_order(1, 'c'), (1, Some(-2)));
+        assert_eq!(vector.insert_with_left_order(9, 'd'), (3, Some(5)));
+        assert_eq!(vector.insert_with_left_order(5, 'e'), (3, Some(5)));
+        assert_eq!(vector.items, ['b', 'c', 'a', 'e', 'd']);
+    }
+
+    #[test]
+    fn add_assign() {
+        let mut vector = IsizeVec::new();
+        vector += (3, 'a');
+        vector += (-1, 'b');
+        vector += (3, 'c');
+        assert_eq!(vector.items, ['b', 'a', 'c']);
+
+        let mut other = IsizeVec::new();
+        other.insert(0, 'd');
+        other.insert(3, 'e');
+        other.insert(8, 'f');
+        vector += other;
+        assert_eq!(vector.items, ['b', 'd', 'a', 'c', 'e', 'f']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 0, 3, 3, 3, 8]);
+    }
+
+    #[test]
+    fn compare_vectors() {
+        let build = |pairs: &[(isize, char)]| pairs.iter().copied().collect::<IsizeVec<_>>();
+
+        let vector = build(&[(0, 'a'), (2, 'b')]);
+        assert_eq!(vector, build(&[(2, 'b'), (0, 'a')]));
+        assert_ne!(vector, build(&[(0, 'a'), (3, 'b')]));
+
+        assert!(vector < build(&[(0, 'a'), (3, 'a')]));
+        assert!(vector > build(&[(0, 'a'), (1, 'z')]));
+        assert!(vector < build(&[(0, 'a'), (2, 'c')]));
+        assert!(vector > build(&[(0, 'a'), (2, 'a')]));
+        assert!(vector > build(&[(0, 'a')]));
+        assert!(vector < build(&[(0, 'a'), (2, 'b'), (2, 'b')]));
+        assert_eq!(vector.cmp(&vector.clone()), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn intersperse_boundaries() {
+        let mut vector = IsizeVec::new();
+        vector.insert(0, "a");
+        vector.insert(0, "b");
+        vector.insert(10, "c");
+        vector.insert(11, "d");
+
+        let mut boundaries = Vec::new();
+        vector.intersperse_boundaries(|a, b| {
+            boundaries.push((a, b));
+            "|"
+        });
+        assert_eq!(boundaries, [(0, 10), (10, 11)]);
+        assert_eq!(vector.items, ["a", "b", "|", "c", "|", "d"]);
+        assert_eq!(
+            vector.iter_order().collect::<Vec<_>>(),
+            [0, 0, 5, 10, 11, 11]
+        );
+        assert!(vector.validate().is_ok());
+    }
 
+    #[test]
+    fn replace_order() {
         let mut vector = IsizeVec::new();
         vector.insert(0, 'a');
         vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(2, 'd');
+        vector.insert(5, 'e');
 
-        assert!(matches!(vector.first_positive(), 0));
+        vector.replace_order(1, 2);
+        assert_eq!(vector.items, ['a', 'd', 'b', 'c', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 2, 2, 2, 5]);
+
+        vector.replace_order(2, 9);
+        assert_eq!(vector.items, ['a', 'e', 'd', 'b', 'c']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 5, 9, 9, 9]);
+
+        vector.replace_order(9, -3);
+        assert_eq!(vector.items, ['d', 'b', 'c', 'a', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-3, -3, -3, 0, 5]);
+
+        vector.replace_order(5, 0);
+        assert_eq!(vector.items, ['d', 'b', 'c', 'a', 'e']);
+        vector.replace_order(-3, 0);
+        assert_eq!(vector.items, ['a', 'e', 'd', 'b', 'c']);
+
+        vector.replace_order(7, 0);
+        assert_eq!(vector.len(), 5);
+        assert!(vector.validate().is_ok());
+    }
+
+    #[test]
+    fn from_vec() {
+        let vector = IsizeVec::from(vec!['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(vector.items, ['a', 'b', 'c', 'd', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+        assert!(vector.validate().is_ok());
+    }
+
+    #[test]
+    fn into_items_with() {
+        #[derive(Debug, PartialEq)]
+        struct Task(isize, &'static str);
 
         let mut vector = IsizeVec::new();
-        vector.insert(0, 'a');
-        vector.insert(0, 'b');
-        vector.insert(0, 'c');
+        vector.insert(4, "write");
+        vector.insert(-1, "plan");
+        vector.insert(9, "ship");
 
-        assert!(matches!(vector.first_positive(), 0));
+        assert_eq!(
+            vector.into_items_with(Task),
+            [Task(-1, "plan"), Task(4, "write"), Task(9, "ship")]
+        );
     }
 
     #[test]
-    fn find_first_right_of() {
+    fn retain_into() {
         let mut vector = IsizeVec::new();
-        vector.insert(0, 'a');
-        vector.insert(1, 'a');
+        for (relative, item) in (-3..4).zip(1..) {
+            vector.insert(relative, item);
+        }
 
-        assert!(matches!(vector.first_right_of(1), 2));
+        let mut removed = vec![(100, 0)];
+        vector.retain_into(|item| item % 3 == 0, &mut removed);
+        assert_eq!(vector.items, [3, 6]);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [-1, 2]);
+        assert_eq!(
+            removed,
+            [(100, 0), (-3, 1), (-2, 2), (0, 4), (1, 5), (3, 7)]
+        );
+    }
 
+    #[test]
+    fn binary_search_by_key() {
+        let mut vector = IsizeVec::new();
+        for &(name, score) in &[("c", 30), ("a", 10), ("b", 20), ("d", 40)] {
+            vector.insert(score as isize, (String::from(name), score));
+        }
+
+        assert_eq!(vector.binary_search_by_key(&20, |&(_, score)| score), Ok(1));
+        assert_eq!(vector[1].0, "b");
+        assert_eq!(
+            vector.binary_search_by_key(&25, |&(_, score)| score),
+            Err(2)
+        );
+    }
+
+    #[test]
+    fn drain_page() {
+        let mut vector = IsizeVec::new();
+        for (relative, item) in [(4, 'e'), (0, 'a'), (2, 'c'), (1, 'b'), (3, 'd')].iter() {
+            vector.insert(*relative, *item);
+        }
+
+        assert_eq!(vector.drain_page(2), [(0, 'a'), (1, 'b')]);
+        assert_eq!(vector.drain_page(2), [(2, 'c'), (3, 'd')]);
+        assert_eq!(vector.drain_page(2), [(4, 'e')]);
+        assert!(vector.drain_page(2).is_empty());
+        assert!(vector.is_empty());
+    }
+
+    #[test]
+    fn order_index() {
+        let mut vector = IsizeVec::new();
+        assert!(vector.order_index().is_empty());
+
+        for relative in &[-2, 0, 0, 0, 3, 7, 7] {
+            vector.insert(*relative, ());
+        }
+        let index = vector.order_index();
+        assert_eq!(
+            index
+                .iter()
+                .map(|(&k, v)| (k, v.clone()))
+                .collect::<Vec<_>>(),
+            [(-2, 0..1), (0, 1..4), (3, 4..5), (7, 5..7)]
+        );
+
+        let mut next = 0;
+        for (&relative, span) in &index {
+            assert_eq!(span.start, next);
+            assert!(vector
+                .iter_order()
+                .skip(span.start)
+                .take(span.len())
+                .all(|x| x == relative));
+            next = span.end;
+        }
+        assert_eq!(next, vector.len());
+    }
+
+    #[test]
+    fn weighted_index() {
+        let mut vector = IsizeVec::new();
+        for relative in &[-5, 0, 1, 3] {
+            vector.insert(*relative, ());
+        }
+
+        assert_eq!(vector.weighted_index(0.0), Some(2));
+        assert_eq!(vector.weighted_index(0.24), Some(2));
+        assert_eq!(vector.weighted_index(0.25), Some(3));
+        assert_eq!(vector.weighted_index(0.999), Some(3));
+        assert_eq!(vector.weighted_index(1.0), None);
+        assert_eq!(vector.weighted_index(-0.1), None);
+        assert_eq!(vector.weighted_index(f64::NAN), None);
+
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, ());
+        vector.insert(0, ());
+        assert_eq!(vector.weighted_index(0.5), None);
+        assert_eq!(IsizeVec::<()>::new().weighted_index(0.5), None);
+    }
+
+    #[test]
+    fn pair_refs() {
         let mut vector = IsizeVec::new();
-        vector.insert(0, 'a');
         vector.insert(1, 'a');
-        vector.insert(2, 'a');
+        vector.insert(-1, 'b');
+        vector[1] = 'c';
 
-        assert!(matches!(vector.first_right_of(1), 2));
+        let pairs = vector.pair_refs();
+        assert_eq!(pairs, [(-1, &'b'), (1, &'c')]);
+        assert!(std::ptr::eq(pairs[1].1, &vector[1]));
+    }
+
+    #[test]
+    fn split_off_by_order() {
+        let build = || {
+            let mut vector = IsizeVec::new();
+            for (relative, item) in [(-2, 'a'), (0, 'b'), (0, 'c'), (3, 'd')].iter() {
+                vector.insert(*relative, *item);
+            }
+            vector
+        };
+
+        let mut vector = build();
+        let upper = vector.split_off_by_order(-5);
+        assert!(vector.is_empty());
+        assert_eq!(upper.items, ['a', 'b', 'c', 'd']);
 
+        let mut vector = build();
+        let upper = vector.split_off_by_order(4);
+        assert_eq!(vector.items, ['a', 'b', 'c', 'd']);
+        assert!(upper.is_empty());
+
+        let mut vector = build();
+        let upper = vector.split_off_by_order(0);
+        assert_eq!(vector.items, ['a']);
+        assert_eq!(upper.items, ['b', 'c', 'd']);
+        assert_eq!(upper.iter_order().collect::<Vec<_>>(), [0, 0, 3]);
+    }
+
+    #[test]
+    fn is_compacted() {
         let mut vector = IsizeVec::new();
+        assert!(vector.is_compacted());
+
         vector.insert(0, 'a');
-        vector.insert(2, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        vector.insert(2, 'd');
+        assert!(vector.is_compacted());
 
-        assert!(matches!(vector.first_right_of(1), 1));
+        vector.insert(isize::MAX, 'e');
+        vector.insert(-7, 'f');
+        assert!(!vector.is_compacted());
+
+        vector.compact_orders();
+        assert!(vector.is_compacted());
+        assert_eq!(vector.items, ['f', 'a', 'b', 'c', 'd', 'e']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0, 1, 2, 2, 3, 4]);
 
         let mut vector = IsizeVec::new();
-        vector.insert(-101, 'a');
-        vector.insert(-100, 'b');
+        vector.insert(3, 'a');
+        assert!(!vector.is_compacted());
+        vector.compact_orders();
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [0]);
+    }
 
-        assert!(matches!(vector.first_right_of(0), 2));
-        assert!(matches!(vector.first_right_of(-100), 2));
-        assert!(matches!(vector.first_right_of(-101), 1));
-        assert!(matches!(vector.first_right_of(-1000), 0));
+    #[test]
+    fn take_and_skip_while_order() {
+        let mut vector = IsizeVec::new();
+        vector.insert(-1, 'a');
+        vector.insert(2, 'b');
+        vector.insert(2, 'c');
+        vector.insert(6, 'd');
+
+        assert_eq!(vector.take_while_order(-5).count(), 0);
+        assert!(vector.skip_while_order(-5).eq(vector.iter()));
+        assert!(vector.take_while_order(7).eq(vector.iter()));
+        assert_eq!(vector.skip_while_order(7).count(), 0);
+        assert_eq!(vector.take_while_order(2).collect::<String>(), "a");
+        assert_eq!(vector.skip_while_order(2).collect::<String>(), "bcd");
+    }
+
+    #[test]
+    fn insert_exact() {
+        use super::OrderError;
 
         let mut vector = IsizeVec::new();
-        vector.insert(0, 'a');
-        vector.insert(0, 'b');
+        assert_eq!(vector.insert_exact(0, 3, 'a'), Ok(()));
+        vector.insert(5, 'b');
+        vector.insert(5, 'c');
 
-        assert!(matches!(vector.first_right_of(0), 2));
+        assert_eq!(vector.insert_exact(2, 5, 'd'), Ok(()));
+        assert_eq!(vector.insert_exact(1, 4, 'e'), Ok(()));
+        assert_eq!(vector.insert_exact(5, 8, 'f'), Ok(()));
+        assert_eq!(vector.items, ['a', 'e', 'b', 'd', 'c', 'f']);
+
+        assert_eq!(vector.insert_exact(0, 4, 'g'), Err(OrderError::Unsorted));
+        assert_eq!(vector.insert_exact(3, 6, 'g'), Err(OrderError::Unsorted));
+        assert_eq!(vector.insert_exact(6, 7, 'g'), Err(OrderError::Unsorted));
+        assert_eq!(vector.items, ['a', 'e', 'b', 'd', 'c', 'f']);
+        assert_eq!(vector.iter_order().collect::<Vec<_>>(), [3, 4, 5, 5, 5, 8]);
+    }
+
+    #[test]
+    fn debug_pairs() {
+        let mut vector = IsizeVec::new();
+        assert_eq!(format!("{:?}", vector), "IsizeVec []");
+
+        vector.insert(10, 'c');
+        vector.insert(5, 'a');
+        vector.insert(5, 'b');
+        assert_eq!(
+            format!("{:?}", vector),
+            "IsizeVec [(5, 'a'), (5, 'b'), (10, 'c')]"
+        );
+    }
+
+    #[test]
+    fn merge_sorted_keeps_capacity() {
+        let mut vector = IsizeVec::with_capacity(100);
+        vector.insert(0, 0);
+        vector.insert(5, 5);
+        vector.insert_batch(vec![(1, 1), (7, 7), (-3, -3)]);
+        vector.merge_presorted(vec![(6, 6), (9, 9)]);
+
+        assert!(vector.items_capacity() >= 100);
+        assert!(vector.orders_capacity() >= 100);
+        assert_eq!(vector.items, [-3, 0, 1, 5, 6, 7, 9]);
+        assert_eq!(
+            vector.iter_order().collect::<Vec<_>>(),
+            [-3, 0, 1, 5, 6, 7, 9]
+        );
+    }
+
+    #[test]
+    fn set_order_unchanged_applies_tiebreak() {
+        use super::{IsizeVecBuilder, Tiebreak};
+
+        let mut vector = IsizeVecBuilder::new().tiebreak(Tiebreak::Prepend).build();
+        vector.insert(1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        assert_eq!(vector.items, ['c', 'b', 'a']);
+        assert_eq!(vector.set_order(2, 1), 0);
+        assert_eq!(vector.items, ['a', 'c', 'b']);
+
+        let mut vector = IsizeVecBuilder::new().tiebreak(Tiebreak::Append).build();
+        vector.insert(1, 'a');
+        vector.insert(1, 'b');
+        vector.insert(1, 'c');
+        assert_eq!(vector.set_order(0, 1), 2);
+        assert_eq!(vector.items, ['b', 'c', 'a']);
+    }
+
+    #[test]
+    fn balance_capacity_mutations() {
+        use super::IsizeVecBuilder;
+
+        let build = || {
+            let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+            for value in 0..10u8 {
+                vector.insert(isize::from(value / 2), value);
+            }
+            assert!(vector.balanced_capacity());
+            vector
+        };
+
+        let mut vector = build();
+        vector.retain_into(|&item| item % 3 == 0, &mut Vec::new());
+        assert!(vector.balanced_capacity());
+
+        let mut vector = build();
+        vector.coalesce(|&left, &right| if right == left + 1 { Some(left) } else { None });
+        assert!(vector.balanced_capacity());
+
+        let mut vector = IsizeVecBuilder::new().balance_capacity(true).build();
+        vector.merge_keep_last((0..10).map(|x| (x, x as u8)));
+        assert!(vector.balanced_capacity());
+
+        let (matching, rest) = build().partition(|&item| item < 3);
+        assert!(matching.balanced_capacity());
+        assert!(rest.balanced_capacity());
+
+        let mapped = build().filter_map(|_, &item| Some(item));
+        assert!(mapped.balanced_capacity());
+
+        let mut vector = build();
+        vector.intersperse_boundaries(|_, _| 0);
+        assert!(vector.balanced_capacity());
+
+        let mut vector = build();
+        vector.merge_presorted((0..20).map(|x| (x, 0)).collect());
+        vector += build();
+        vector.extend_from_slice(&[1, 2, 3]);
+        vector.push_back(isize::MAX, 4).unwrap();
+        vector.reset_to((0..30).map(|x| (x, 0)));
+        vector.dedup_items_keep_last();
+        assert!(vector.balanced_capacity());
+
+        let mut vector = build();
+        vector.drain_sorted().take(3).for_each(drop);
+        vector.retain(|&item| item > 5);
+        assert!(vector.balanced_capacity());
+        assert!(vector.split_off_by_order(4).balanced_capacity());
+        assert!(vector.balanced_capacity());
+    }
+
+    #[test]
+    fn auto_traits() {
+        use std::panic::{RefUnwindSafe, UnwindSafe};
+
+        fn assert_traits<T: Send + Sync + UnwindSafe + RefUnwindSafe>() {}
+        assert_traits::<IsizeVec<u8>>();
+    }
+
+    #[quickcheck_macros::quickcheck]
+    fn retain_into_keeps_sequence(pairs: Vec<(i8, u8)>) {
+        let mut vector = IsizeVec::new();
+        for &(relative, item) in &pairs {
+            vector.insert(relative as isize, item);
+        }
+        let (expected_kept, expected_removed): (Vec<_>, Vec<_>) = vector
+            .iter_order()
+            .zip(vector.iter().copied())
+            .partition(|&(_, item)| item % 3 == 0);
+
+        let mut removed = Vec::new();
+        vector.retain_into(|item| item % 3 == 0, &mut removed);
+        assert_eq!(removed, expected_removed);
+        assert!(vector
+            .iter_order()
+            .zip(vector.iter().copied())
+            .eq(expected_kept));
     }
 }